@@ -4,6 +4,7 @@ extern crate fnv;
 extern crate twox_hash;
 extern crate t1ha;
 extern crate bitvec;
+extern crate kmer_hasher;
 
 use twox_hash::XxHash64;
 
@@ -18,7 +19,7 @@ use bitvec::prelude::*;
 
 use std::ops::BitXor;
 
-use std::mem;
+use kmer_hasher::{CONVERSION, kmerhash_unchecked, hash4_unchecked, hash8_unchecked, hash_batch};
 
 #[macro_use]
 extern crate lazy_static;
@@ -27,37 +28,6 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 lazy_static! {
 
-    static ref CONVERSION: [u64; 256] = {
-
-        let mut conversion: [u64; 256] = [1; 256];
-        conversion[65]  = 7;
-        conversion[97]  = 7;
-        conversion[84]  = 0;
-        conversion[116] = 0;
-        conversion[67]  = 5;
-        conversion[99]  = 5;
-        conversion[71]  = 2;
-        conversion[103] = 2;
-
-        conversion
-    };
-
-    static ref CONVERSION_I: [i64; 256] = {
-
-        let mut conversion: [i64; 256] = [1; 256];
-        conversion[65]  = 7;
-        conversion[97]  = 7;
-        conversion[84]  = 0;
-        conversion[116] = 0;
-        conversion[67]  = 5;
-        conversion[99]  = 5;
-        conversion[71]  = 2;
-        conversion[103] = 2;
-
-        conversion
-    };
-
-
     static ref KMERS: Vec<Vec<u8>> = {
 
         let mut rng = rand::thread_rng();
@@ -247,7 +217,7 @@ fn hash_vec_u8(c: &mut Criterion) {
         let chunks = kmers.chunks_exact(4);
 
         for kmer in chunks.remainder() {
-            hashes.push(convert_kmer_to_bits2_foreach(&kmer));
+            hashes.push(kmerhash_unchecked(&kmer));
         }
 
         for chunk in chunks {
@@ -261,25 +231,32 @@ fn hash_vec_u8(c: &mut Criterion) {
     group.bench_function("3bit2_hash4", |b| b.iter(|| {
         let kmers = KMERS.clone();
         let mut hashes = Vec::with_capacity(KMERS.len());
-        let chunks = kmers.chunks_exact(4);
+        hash_batch(&kmers, &mut hashes).unwrap();
+        }));
 
-        for kmer in chunks.remainder() {
-            hashes.push(convert_kmer_to_bits2_foreach(&kmer));
-        }
+    // Four lanes per call, against 3bit2fe's one k-mer at a time
+    group.bench_function("3bit2_hash4_lanes", |b| b.iter(|| {
+        let mut hashes = Vec::with_capacity(KMERS.len());
+        for chunk in KMERS.chunks_exact(4) {
+            let h = hash4_unchecked(black_box((&chunk[0][..], &chunk[1][..], &chunk[2][..], &chunk[3][..])));
+            hashes.extend_from_slice(&[h.0, h.1, h.2, h.3]);
+        }}));
 
-        for chunk in chunks {
-            let i: (u64, u64, u64, u64) = hash4(&chunk[0], &chunk[1], &chunk[2], &chunk[3]);
-            hashes.push(i.0);
-            hashes.push(i.1);
-            hashes.push(i.2);
-            hashes.push(i.3);
+    group.bench_function("3bit2_hash8_lanes", |b| b.iter(|| {
+        let mut hashes = Vec::with_capacity(KMERS.len());
+        for chunk in KMERS.chunks_exact(8) {
+            let mut lanes: [&[u8]; 8] = [&[]; 8];
+            for (lane, kmer) in lanes.iter_mut().zip(chunk.iter()) {
+                *lane = kmer;
+            }
+            hashes.extend_from_slice(&hash8_unchecked(black_box(lanes)));
         }}));
 
     group.bench_function("3bit2fe", |b| b.iter(|| {
         let kmers = KMERS.clone();
         let mut hashes = Vec::with_capacity(KMERS.len());
         for kmer in kmers {
-            hashes.push(convert_kmer_to_bits2_foreach(&kmer));
+            hashes.push(kmerhash_unchecked(&kmer));
         }}));
 
     group.bench_function("3bit2", |b| b.iter(|| {
@@ -467,18 +444,6 @@ fn convert_kmer_to_bits2(kmer: &[u8]) -> u64 {
     bits
 }
 
-#[inline(always)]
-fn convert_kmer_to_bits2_foreach(kmer: &[u8]) -> u64 {
-    let mut bits: u64 = 0;
-    bits = bits.wrapping_add(CONVERSION[usize::from(kmer[0])]);
-    &kmer[1..].iter().for_each(|base| {
-        bits <<= 3;
-        bits = bits.wrapping_add(CONVERSION[usize::from(*base)]);
-    });
-    bits
-}
-
-
 // Previous version, replaced because .wrapping_add is faster
 // Here for legacy reasons...
 fn convert_kmer_to_bits2_1(kmer: &[u8]) -> u64 {
@@ -533,37 +498,6 @@ fn change_bits(char: u8, n: usize, bits: &mut u64) -> u64 {
     *bits
 }
 
-#[cfg(target_arch = "x86_64")]
-use std::arch::x86_64::*;
-#[cfg(target_arch = "x86")]
-use std::arch::x86::*;
-
-// AVX can calc 4 at a time
-fn hash4(k1: &[u8], k2: &[u8], k3: &[u8], k4: &[u8]) -> (u64, u64, u64, u64) {
-    unsafe {
-        let mut hashes = _mm256_setzero_si256();
-        let shift = _mm_set1_epi64x(3);
-
-        let mut add = _mm256_set_epi64x(CONVERSION_I[usize::from(k1[0])],
-                                        CONVERSION_I[usize::from(k2[0])],
-                                        CONVERSION_I[usize::from(k3[0])],
-                                        CONVERSION_I[usize::from(k4[0])]);
-        
-        hashes = _mm256_add_epi64(hashes, add);
-
-        for i in 1..k1.len() {
-            hashes = _mm256_sll_epi64(hashes, shift);
-            add = _mm256_set_epi64x(CONVERSION_I[usize::from(k1[i])],
-                                    CONVERSION_I[usize::from(k2[i])],
-                                    CONVERSION_I[usize::from(k3[i])],
-                                    CONVERSION_I[usize::from(k4[i])]);
-            hashes = _mm256_add_epi64(hashes, add);
-        }
-
-        mem::transmute(hashes)
-    }
-}
-
 // A is 7
 // T is 0
 // C is 5
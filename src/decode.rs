@@ -0,0 +1,40 @@
+use crate::error::check_k;
+use crate::{KmerError, KmerWord, MAX_K, MAX_K_128};
+
+// 3-bit code back to its base. Codes no base encodes to decode as N.
+const DECODE: [u8; 8] = [b'T', b'N', b'G', b'N', b'N', b'C', b'N', b'A'];
+
+/// Turn a 3-bit hash back into the k-mer it was made from.
+///
+/// Bases come back upper case and as DNA, so U comes back as `T`. Anything
+/// that was hashed as N (ambiguity codes, non-nucleotide bytes) comes back as
+/// `N`.
+pub fn decode(khash: u64, k: usize) -> Result<Vec<u8>, KmerError> {
+    check_k(k, MAX_K)?;
+    let mut kmer = vec![0; k];
+    decode_word(khash, &mut kmer);
+    Ok(kmer)
+}
+
+/// [`decode`] into an existing buffer, taking k from its length.
+pub fn decode_into(khash: u64, kmer: &mut [u8]) -> Result<(), KmerError> {
+    check_k(kmer.len(), MAX_K)?;
+    decode_word(khash, kmer);
+    Ok(())
+}
+
+/// [`decode`] for k-mers encoded into a `u128`.
+pub fn decode_u128(khash: u128, k: usize) -> Result<Vec<u8>, KmerError> {
+    check_k(k, MAX_K_128)?;
+    let mut kmer = vec![0; k];
+    decode_word(khash, &mut kmer);
+    Ok(kmer)
+}
+
+#[inline(always)]
+pub(crate) fn decode_word<W: KmerWord>(mut khash: W, kmer: &mut [u8]) {
+    for base in kmer.iter_mut().rev() {
+        *base = DECODE[khash.low_code() as usize];
+        khash = khash >> 3;
+    }
+}
@@ -0,0 +1,209 @@
+use std::cmp::min;
+
+use crate::error::check_k;
+use crate::iupac::is_ambiguous;
+use crate::{calc_rc, kmerhash_unchecked, KmerError, CONVERSION, RC_CONVERSION};
+
+/// How bases are packed into a hash.
+///
+/// An encoding gives every byte a code of [`BITS`](Encoding::BITS) bits and
+/// says what each code's complement is. The first base of a k-mer ends up in
+/// the most significant position, as with [`kmerhash`](crate::kmerhash).
+///
+/// The rolling iterators take an encoding as a type parameter, defaulting to
+/// [`ThreeBit`], and the provided methods hash single k-mers into a `u64`.
+pub trait Encoding {
+    /// Bits per base.
+    const BITS: usize;
+
+    /// Longest k-mer that fits in a `u64`.
+    const MAX_K: usize = 64 / Self::BITS;
+
+    /// Byte to code lookup table.
+    fn table() -> &'static [u64; 256];
+
+    /// Code of the complement of the base `code` stands for.
+    fn complement(code: u64) -> u64;
+
+    #[inline(always)]
+    fn code(base: u8) -> u64 {
+        Self::table()[usize::from(base)]
+    }
+
+    /// Code of the complement of `base`.
+    #[inline(always)]
+    fn rc_code(base: u8) -> u64 {
+        Self::complement(Self::code(base))
+    }
+
+    /// Hash a k-mer, failing unless `1 <= k <= MAX_K`.
+    #[inline(always)]
+    fn hash(kmer: &[u8]) -> Result<u64, KmerError> {
+        check_k(kmer.len(), Self::MAX_K)?;
+        Ok(Self::hash_unchecked(kmer))
+    }
+
+    /// [`hash`](Encoding::hash) without the length check.
+    #[inline(always)]
+    fn hash_unchecked(kmer: &[u8]) -> u64 {
+        kmer.iter().fold(0, |hash, base| (hash << Self::BITS) | Self::code(*base))
+    }
+
+    /// Reverse complement of the hash of a k-mer. Encodings with no code of
+    /// their own for N, like [`TwoBit`], only get this right for plain ACGT.
+    #[inline(always)]
+    fn reverse_complement(k: usize, mut khash: u64) -> u64 {
+        let mut rc = 0;
+        for _ in 0..k {
+            rc = (rc << Self::BITS) | Self::complement(khash & ((1 << Self::BITS) - 1));
+            khash >>= Self::BITS;
+        }
+        rc
+    }
+
+    /// The smaller of the hashes of a k-mer and its reverse complement.
+    #[inline(always)]
+    fn hash_smallest(kmer: &[u8]) -> Result<u64, KmerError> {
+        let khash = Self::hash(kmer)?;
+        Ok(min(khash, Self::reverse_complement(kmer.len(), khash)))
+    }
+}
+
+/// A => 00, C => 01, G => 10, T => 11.
+///
+/// Packs 32 bases into a `u64`, but has no room for N: it, and every other
+/// byte that isn't ACGT or U, is encoded as A on both strands.
+/// [`rc_code`](Encoding::rc_code) and
+/// [`hash_smallest`](Encoding::hash_smallest) work from the bases, so
+/// canonical hashes are strand-independent with N in them too.
+/// [`reverse_complement`](Encoding::reverse_complement) only has the hash to
+/// go on and takes every 00 for A, so it only matches the reverse strand's
+/// hash for k-mers of plain ACGT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TwoBit;
+
+/// The crate's own encoding, see [`CONVERSION`]: A => 111, T => 000,
+/// C => 101, G => 010 and N => 001.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ThreeBit;
+
+/// The 3-bit encoding the bitvec benchmarks use: A => 000, T => 011,
+/// C => 001, G => 010 and N => 111. U is encoded as T and other bytes as N.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ThreeBitBitvec;
+
+lazy_static! {
+    static ref TWO_BIT: [u64; 256] = {
+
+        let mut conversion: [u64; 256] = [0; 256];
+        conversion[67]  = 1;
+        conversion[99]  = 1;
+        conversion[71]  = 2;
+        conversion[103] = 2;
+        conversion[84]  = 3;
+        conversion[116] = 3;
+        conversion[85]  = 3;
+        conversion[117] = 3;
+
+        conversion
+    };
+
+    static ref THREE_BIT_BITVEC: [u64; 256] = {
+
+        let mut conversion: [u64; 256] = [7; 256];
+        conversion[65]  = 0;
+        conversion[97]  = 0;
+        conversion[84]  = 3;
+        conversion[116] = 3;
+        conversion[85]  = 3;
+        conversion[117] = 3;
+        conversion[67]  = 1;
+        conversion[99]  = 1;
+        conversion[71]  = 2;
+        conversion[103] = 2;
+
+        conversion
+    };
+}
+
+impl Encoding for TwoBit {
+    const BITS: usize = 2;
+
+    #[inline(always)]
+    fn table() -> &'static [u64; 256] {
+        &TWO_BIT
+    }
+
+    #[inline(always)]
+    fn complement(code: u64) -> u64 {
+        code ^ 3
+    }
+
+    // N is A on the reverse strand as well, not its complement T
+    #[inline(always)]
+    fn rc_code(base: u8) -> u64 {
+        if is_ambiguous(base) {
+            0
+        } else {
+            Self::code(base) ^ 3
+        }
+    }
+
+    #[inline(always)]
+    fn hash_smallest(kmer: &[u8]) -> Result<u64, KmerError> {
+        let khash = Self::hash(kmer)?;
+        let rc = kmer.iter().rev().fold(0, |rc, base| (rc << 2) | Self::rc_code(*base));
+        Ok(min(khash, rc))
+    }
+}
+
+impl Encoding for ThreeBit {
+    const BITS: usize = 3;
+
+    #[inline(always)]
+    fn table() -> &'static [u64; 256] {
+        &CONVERSION
+    }
+
+    #[inline(always)]
+    fn complement(code: u64) -> u64 {
+        if code == 1 {
+            1
+        } else {
+            code ^ 7
+        }
+    }
+
+    #[inline(always)]
+    fn rc_code(base: u8) -> u64 {
+        RC_CONVERSION[usize::from(base)]
+    }
+
+    #[inline(always)]
+    fn hash_unchecked(kmer: &[u8]) -> u64 {
+        kmerhash_unchecked(kmer)
+    }
+
+    #[inline(always)]
+    fn reverse_complement(k: usize, khash: u64) -> u64 {
+        calc_rc(k, khash)
+    }
+}
+
+impl Encoding for ThreeBitBitvec {
+    const BITS: usize = 3;
+
+    #[inline(always)]
+    fn table() -> &'static [u64; 256] {
+        &THREE_BIT_BITVEC
+    }
+
+    #[inline(always)]
+    fn complement(code: u64) -> u64 {
+        if code == 7 {
+            7
+        } else {
+            code ^ 3
+        }
+    }
+}
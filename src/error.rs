@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::fmt;
+
+/// Why a k-mer couldn't be hashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KmerError {
+    /// The k-mer (or k) was empty.
+    Empty,
+    /// The k-mer is longer than the word it's being packed into can hold.
+    TooLong { len: usize, max: usize },
+    /// Lengths that have to match didn't, such as k-mers hashed together or a
+    /// batch and its output buffer.
+    LengthMismatch { expected: usize, found: usize },
+    /// An ambiguous k-mer stands for more concrete k-mers than allowed.
+    TooAmbiguous { found: usize, max: usize },
+    /// A byte that isn't a nucleotide, see
+    /// [`is_nucleotide`](crate::is_nucleotide).
+    InvalidBase { pos: usize, byte: u8 },
+}
+
+impl fmt::Display for KmerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KmerError::Empty => write!(f, "k-mer is empty"),
+            KmerError::TooLong { len, max } => {
+                write!(f, "k-mer of length {} is longer than the maximum of {}", len, max)
+            }
+            KmerError::LengthMismatch { expected, found } => {
+                write!(f, "expected a length of {}, found {}", expected, found)
+            }
+            KmerError::TooAmbiguous { found, max } => {
+                write!(f, "k-mer expands to {} k-mers, more than the maximum of {}", found, max)
+            }
+            KmerError::InvalidBase { pos, byte } => {
+                write!(f, "byte {:#04x} at position {} is not a nucleotide", byte, pos)
+            }
+        }
+    }
+}
+
+impl Error for KmerError {}
+
+// Shared by every checked API
+#[inline(always)]
+pub(crate) fn check_k(k: usize, max: usize) -> Result<(), KmerError> {
+    if k == 0 {
+        Err(KmerError::Empty)
+    } else if k > max {
+        Err(KmerError::TooLong { len: k, max })
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::{kmerhash_unchecked, mix, MAX_K};
+
+/// [`Hasher`] for k-mer keys such as `Vec<u8>` and `&[u8]`.
+///
+/// Bytes are packed with [`kmerhash`](crate::kmerhash), 21 at a time, and
+/// each word is run through [`mix`]. That makes a hash a handful of table
+/// lookups and two multiplies, with no collisions between distinct k-mers of
+/// the same length up to [`MAX_K`]. Case and the identity of unknown bases
+/// are lost in the encoding, so `ACGN` and `acgr` collide; maps still tell
+/// them apart, they just share a bucket.
+///
+/// Integers, like the length `[u8]` writes before its bytes, are mixed in as
+/// numbers rather than encoded as bases.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KmerHasher {
+    hash: u64,
+}
+
+/// [`BuildHasher`](std::hash::BuildHasher) for [`KmerHasher`], for use as
+/// `HashMap<Vec<u8>, V, KmerBuildHasher>`.
+pub type KmerBuildHasher = BuildHasherDefault<KmerHasher>;
+
+impl Hasher for KmerHasher {
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(MAX_K) {
+            self.hash = mix(self.hash ^ kmerhash_unchecked(chunk));
+        }
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, n: u8) {
+        self.write_u64(u64::from(n));
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, n: u16) {
+        self.write_u64(u64::from(n));
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, n: u32) {
+        self.write_u64(u64::from(n));
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, n: u64) {
+        self.hash = mix(self.hash ^ n);
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, n: u128) {
+        self.write_u64(n as u64);
+        self.write_u64((n >> 64) as u64);
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// [`Hasher`] that passes `u64` keys straight through, for k-mers that are
+/// already encoded.
+///
+/// `HashMap` picks buckets from the low bits and uses the high bits to tell
+/// entries apart, so raw [`kmerhash`](crate::kmerhash) values, which are all
+/// zero at the top, should be [`mix`]ed before they're used as keys.
+///
+/// A lone `u64` or `usize` comes out as it went in. Anything else, from a
+/// tuple of integers to a byte string, is folded in a field or byte at a time,
+/// rotating what came before, so every part counts but nothing is scrambled.
+/// Keys like that are better served by a general purpose hasher.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IdentityHasher {
+    hash: u64,
+}
+
+/// [`BuildHasher`](std::hash::BuildHasher) for [`IdentityHasher`], for use as
+/// `HashMap<u64, V, IdentityBuildHasher>`.
+pub type IdentityBuildHasher = BuildHasherDefault<IdentityHasher>;
+
+impl Hasher for IdentityHasher {
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash = self.hash.rotate_left(8) ^ u64::from(*byte);
+        }
+    }
+
+    // Starting from zero, a single integer is left as it is
+    #[inline(always)]
+    fn write_u64(&mut self, n: u64) {
+        self.hash = self.hash.rotate_left(32) ^ n;
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
@@ -0,0 +1,154 @@
+use std::marker::PhantomData;
+
+use crate::error::check_k;
+use crate::{kmerhash_unchecked, Encoding, KmerError, ThreeBit, CONVERSION, MAX_K};
+
+// 3-bit codes of A, C, G and T, in the order of the bits in BASES
+const CODES: [u64; 4] = [7, 5, 2, 0];
+
+lazy_static! {
+    // Bases each byte can stand for, one bit each for A, C, G and T. Bytes
+    // that aren't nucleotides can stand for anything, as they hash as N.
+    static ref BASES: [u8; 256] = {
+
+        let mut bases: [u8; 256] = [0b1111; 256];
+        for (code, set) in [(b'A', 0b0001), (b'C', 0b0010), (b'G', 0b0100), (b'T', 0b1000),
+                            (b'U', 0b1000),
+                            (b'R', 0b0101), (b'Y', 0b1010), (b'S', 0b0110), (b'W', 0b1001),
+                            (b'K', 0b1100), (b'M', 0b0011), (b'B', 0b1110), (b'D', 0b1101),
+                            (b'H', 0b1011), (b'V', 0b0111)].iter() {
+            bases[usize::from(*code)] = *set;
+            bases[usize::from(code.to_ascii_lowercase())] = *set;
+        }
+
+        bases
+    };
+}
+
+#[inline(always)]
+pub(crate) fn is_ambiguous(base: u8) -> bool {
+    CONVERSION[usize::from(base)] == 1
+}
+
+// One bit per base, first base highest, set for anything but ACGT
+#[inline(always)]
+pub(crate) fn ambiguity_mask(kmer: &[u8]) -> u32 {
+    kmer.iter().fold(0, |mask, base| (mask << 1) | is_ambiguous(*base) as u32)
+}
+
+/// Number of concrete k-mers an ambiguous one stands for, saturating at
+/// `usize::MAX`.
+pub fn expansions(kmer: &[u8]) -> usize {
+    kmer.iter()
+        .map(|base| BASES[usize::from(*base)].count_ones() as usize)
+        .fold(1, usize::saturating_mul)
+}
+
+/// Hash every concrete k-mer an ambiguous k-mer stands for.
+///
+/// Each ambiguity code is replaced by every base it can be, N and
+/// non-nucleotide bytes by all four, so `ACNT` expands to `ACAT`, `ACCT`,
+/// `ACGT` and `ACTT`. Hashes come out in the order A, C, G, T at each position.
+/// A k-mer with no ambiguity codes expands to just its own hash.
+///
+/// Fails if the k-mer is empty, longer than [`MAX_K`], or would expand into
+/// more than `max` k-mers.
+pub fn expand(kmer: &[u8], max: usize) -> Result<Vec<u64>, KmerError> {
+    check_k(kmer.len(), MAX_K)?;
+    let found = expansions(kmer);
+    if found > max {
+        return Err(KmerError::TooAmbiguous { found, max });
+    }
+
+    let mut hashes = vec![0];
+    for base in kmer {
+        let set = BASES[usize::from(*base)];
+        hashes = hashes
+            .iter()
+            .flat_map(|hash| {
+                CODES
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| set & (1 << bit) != 0)
+                    .map(move |(_, code)| (hash << 3) | code)
+            })
+            .collect();
+    }
+
+    Ok(hashes)
+}
+
+/// Hash a k-mer along with the positions of its ambiguous bases.
+///
+/// The hash is [`kmerhash`](crate::kmerhash)'s, with ambiguity codes still
+/// collapsed to N. The mask has one bit per base laid out like the hash, the
+/// first base in bit `k - 1` and the last in bit 0, set wherever the base
+/// isn't A, C, G or T.
+pub fn kmerhash_ambiguous(kmer: &[u8]) -> Result<(u64, u32), KmerError> {
+    check_k(kmer.len(), MAX_K)?;
+    Ok((kmerhash_unchecked(kmer), ambiguity_mask(kmer)))
+}
+
+/// Rolling hash that reports ambiguous positions alongside each hash.
+///
+/// Yields `(position, hash, mask)` for every window, with the hash and mask
+/// as [`kmerhash_ambiguous`] would give them for the default encoding.
+///
+/// Usually used through [`AmbiguousRollingHash`] or
+/// [`TwoBitRollingHash`](crate::TwoBitRollingHash).
+pub struct AmbiguousRolling<'a, E> {
+    seq: &'a [u8],
+    k: usize,
+    mask: u64,
+    pos: usize,
+    hash: u64,
+    ambiguous: u32,
+    encoding: PhantomData<E>,
+}
+
+/// [`AmbiguousRolling`] for the default 3-bit encoding.
+pub type AmbiguousRollingHash<'a> = AmbiguousRolling<'a, ThreeBit>;
+
+impl<'a, E: Encoding> AmbiguousRolling<'a, E> {
+    /// Fails unless `1 <= k <= E::MAX_K`, and k is at most 32 whatever the
+    /// encoding, so that the mask fits in a `u32`.
+    pub fn new(seq: &'a [u8], k: usize) -> Result<AmbiguousRolling<'a, E>, KmerError> {
+        check_k(k, E::MAX_K.min(32))?;
+
+        let primed = (k - 1).min(seq.len());
+        let mut hash = 0;
+        for base in &seq[..primed] {
+            hash = (hash << E::BITS) | E::code(*base);
+        }
+
+        Ok(AmbiguousRolling {
+            seq,
+            k,
+            mask: !0 >> (64 - k * E::BITS),
+            pos: primed,
+            hash,
+            ambiguous: ambiguity_mask(&seq[..primed]),
+            encoding: PhantomData,
+        })
+    }
+}
+
+impl<'a, E: Encoding> Iterator for AmbiguousRolling<'a, E> {
+    type Item = (usize, u64, u32);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(usize, u64, u32)> {
+        let base = *self.seq.get(self.pos)?;
+        self.hash = ((self.hash << E::BITS) | E::code(base)) & self.mask;
+        self.ambiguous = ((self.ambiguous << 1) | is_ambiguous(base) as u32) & (!0 >> (32 - self.k));
+        self.pos += 1;
+        Some((self.pos - self.k, self.hash, self.ambiguous))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.seq.len() - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, E: Encoding> ExactSizeIterator for AmbiguousRolling<'a, E> {}
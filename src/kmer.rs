@@ -0,0 +1,158 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::decode::decode_word;
+use crate::error::check_k;
+use crate::{calc_rc, kmerhash_unchecked, KmerError, KmerWord, Strand, MAX_K};
+
+/// A k-mer of exactly `K` bases, encoded as [`kmerhash`](crate::kmerhash)
+/// would.
+///
+/// Carrying k in the type means a 21-mer can't be compared with, or
+/// looked up as, a 15-mer. Ordering and hashing are those of the encoded
+/// word. `K` must be between 1 and [`MAX_K`]; constructors fail otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Kmer<const K: usize> {
+    bits: u64,
+}
+
+impl<const K: usize> Kmer<K> {
+    /// Encode a k-mer, failing unless it is exactly `K` bases long.
+    pub fn from_bytes(kmer: &[u8]) -> Result<Kmer<K>, KmerError> {
+        check_k(K, MAX_K)?;
+        if kmer.len() != K {
+            return Err(KmerError::LengthMismatch { expected: K, found: kmer.len() });
+        }
+        Ok(Kmer { bits: kmerhash_unchecked(kmer) })
+    }
+
+    /// Wrap a hash from [`kmerhash`](crate::kmerhash), keeping its low
+    /// `3 * K` bits.
+    pub fn from_hash(khash: u64) -> Result<Kmer<K>, KmerError> {
+        check_k(K, MAX_K)?;
+        Ok(Kmer { bits: khash & u64::mask(K) })
+    }
+
+    /// The encoded word.
+    pub fn word(self) -> u64 {
+        self.bits
+    }
+
+    /// Decode back into the sequence, see [`decode`](crate::decode).
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut kmer = vec![0; K];
+        decode_word(self.bits, &mut kmer);
+        kmer
+    }
+
+    /// Reverse complement, see [`calc_rc`].
+    pub fn revcomp(self) -> Kmer<K> {
+        Kmer { bits: calc_rc(K, self.bits) }
+    }
+
+    /// The smaller of the k-mer and its reverse complement, and the strand it
+    /// came from. Palindromes report [`Strand::Forward`].
+    pub fn canonical(self) -> (Kmer<K>, Strand) {
+        let rc = self.revcomp();
+        if rc < self {
+            (rc, Strand::Reverse)
+        } else {
+            (self, Strand::Forward)
+        }
+    }
+}
+
+impl<const K: usize> fmt::Display for Kmer<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_bases(self.bits, K, f)
+    }
+}
+
+/// [`Kmer`] with k chosen at runtime, for k up to [`MAX_K`].
+///
+/// Ordered by k first and then by the encoded word, so k-mers of different
+/// lengths never compare equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DynKmer {
+    k: usize,
+    bits: u64,
+}
+
+impl DynKmer {
+    /// Encode a k-mer, failing if it is empty or longer than [`MAX_K`].
+    pub fn from_bytes(kmer: &[u8]) -> Result<DynKmer, KmerError> {
+        check_k(kmer.len(), MAX_K)?;
+        Ok(DynKmer { k: kmer.len(), bits: kmerhash_unchecked(kmer) })
+    }
+
+    /// Wrap a hash from [`kmerhash`](crate::kmerhash) of a k-mer of length
+    /// `k`, keeping its low `3 * k` bits.
+    pub fn from_hash(khash: u64, k: usize) -> Result<DynKmer, KmerError> {
+        check_k(k, MAX_K)?;
+        Ok(DynKmer { k, bits: khash & u64::mask(k) })
+    }
+
+    pub fn k(self) -> usize {
+        self.k
+    }
+
+    /// The encoded word.
+    pub fn word(self) -> u64 {
+        self.bits
+    }
+
+    /// Decode back into the sequence, see [`decode`](crate::decode).
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut kmer = vec![0; self.k];
+        decode_word(self.bits, &mut kmer);
+        kmer
+    }
+
+    /// Reverse complement, see [`calc_rc`].
+    pub fn revcomp(self) -> DynKmer {
+        DynKmer { k: self.k, bits: calc_rc(self.k, self.bits) }
+    }
+
+    /// The smaller of the k-mer and its reverse complement, and the strand it
+    /// came from. Palindromes report [`Strand::Forward`].
+    pub fn canonical(self) -> (DynKmer, Strand) {
+        let rc = self.revcomp();
+        if rc < self {
+            (rc, Strand::Reverse)
+        } else {
+            (self, Strand::Forward)
+        }
+    }
+}
+
+impl fmt::Display for DynKmer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_bases(self.bits, self.k, f)
+    }
+}
+
+impl<const K: usize> From<Kmer<K>> for DynKmer {
+    fn from(kmer: Kmer<K>) -> DynKmer {
+        DynKmer { k: K, bits: kmer.bits }
+    }
+}
+
+impl<const K: usize> TryFrom<DynKmer> for Kmer<K> {
+    type Error = KmerError;
+
+    /// Fails unless the k-mer is `K` bases long.
+    fn try_from(kmer: DynKmer) -> Result<Kmer<K>, KmerError> {
+        if kmer.k != K {
+            return Err(KmerError::LengthMismatch { expected: K, found: kmer.k });
+        }
+        Ok(Kmer { bits: kmer.bits })
+    }
+}
+
+// Decoded bases, without allocating
+fn fmt_bases(bits: u64, k: usize, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut kmer = [0; MAX_K];
+    decode_word(bits, &mut kmer[..k]);
+    // Decoding only ever produces ACGTN
+    f.write_str(std::str::from_utf8(&kmer[..k]).unwrap())
+}
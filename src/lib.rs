@@ -0,0 +1,300 @@
+//! 3-bit k-mer hashing.
+//!
+//! Each nucleotide is packed into three bits, so a k-mer of up to 21 bases
+//! fits in a single `u64`. The packing is invertible and the codes are chosen
+//! so the reverse complement can be computed with bit operations rather than
+//! by re-reading the sequence.
+
+#[macro_use]
+extern crate lazy_static;
+
+use std::cmp::min;
+
+mod decode;
+mod encoding;
+mod error;
+mod hasher;
+mod iupac;
+mod kmer;
+mod long;
+mod minimizer;
+mod mix;
+mod protein;
+mod rolling;
+mod simd;
+mod twobit;
+mod word;
+
+pub use decode::{decode, decode_into, decode_u128};
+pub use encoding::{Encoding, ThreeBit, ThreeBitBitvec, TwoBit};
+pub use error::KmerError;
+pub use hasher::{IdentityBuildHasher, IdentityHasher, KmerBuildHasher, KmerHasher};
+pub use iupac::{expand, expansions, kmerhash_ambiguous, AmbiguousRolling, AmbiguousRollingHash};
+pub use kmer::{DynKmer, Kmer};
+pub use long::{LongCanonicalRollingHash, LongKmer, LongRollingHash};
+pub use minimizer::Minimizers;
+pub use mix::{decode_mixed, kmerhash_mixed, mix, unmix};
+pub use protein::{
+    peptidehash, peptidehash_batch, peptidehash_batch_into, peptidehash_u128, peptidehash_u128_unchecked,
+    peptidehash_unchecked, PeptideRolling, PeptideRollingHash, PeptideRollingHash128, MAX_PEPTIDE_K,
+    MAX_PEPTIDE_K_128,
+};
+pub use rolling::{
+    CanonicalRolling, CanonicalRollingHash, CanonicalRollingHash128, Filtered, Flagged, NPolicy,
+    Rolling, RollingHash, RollingHash128,
+};
+pub use simd::{
+    hash4, hash4_unchecked, hash8, hash8_unchecked, hash_batch, hash_batch_into, hash_sequence,
+    hash_sequence_into,
+};
+pub use twobit::{kmerhash_2bit, kmerhash_smallest_2bit, CanonicalTwoBitRollingHash, TwoBitRollingHash};
+pub use word::KmerWord;
+
+/* A => 111
+ * T => 000
+ * C => 101
+ * G => 010
+ * N => 001
+ *
+ * A, C, G and T are bit palindromes, so reversing a word and inverting it
+ * complements every base. N has no 3-bit code that is its own complement
+ * under that trick: 001 comes back as 011, which calc_rc folds back to 001.
+ *
+ * U is hashed as T, so RNA and its DNA transcript hash the same. Every other
+ * byte is hashed as N. IUPAC ambiguity codes are nucleotides that happen to
+ * be unknown; gaps, stops, whitespace and anything else are not nucleotides
+ * at all, see is_nucleotide, and kmerhash_checked rejects them. The iupac
+ * module can expand ambiguity codes or report where they are instead.
+*/
+
+lazy_static! {
+    /// Byte to 3-bit code lookup table. Upper and lower case bases share a
+    /// code and U shares T's, anything else (including non-nucleotide bytes)
+    /// is encoded as N.
+    pub static ref CONVERSION: [u64; 256] = {
+
+        let mut conversion: [u64; 256] = [1; 256];
+        conversion[65]  = 7;
+        conversion[97]  = 7;
+        conversion[84]  = 0;
+        conversion[116] = 0;
+        conversion[85]  = 0;
+        conversion[117] = 0;
+        conversion[67]  = 5;
+        conversion[99]  = 5;
+        conversion[71]  = 2;
+        conversion[103] = 2;
+
+        conversion
+    };
+
+    // Code of each byte's complement, for building reverse complement words
+    // a base at a time. N is its own complement.
+    pub(crate) static ref RC_CONVERSION: [u64; 256] = {
+
+        let mut conversion: [u64; 256] = [1; 256];
+        conversion[65]  = 0;
+        conversion[97]  = 0;
+        conversion[84]  = 7;
+        conversion[116] = 7;
+        conversion[85]  = 7;
+        conversion[117] = 7;
+        conversion[67]  = 2;
+        conversion[99]  = 2;
+        conversion[71]  = 5;
+        conversion[103] = 5;
+
+        conversion
+    };
+
+    /// Byte to 5-bit amino acid code lookup table, for peptide hashing. The
+    /// 20 standard residues come first in alphabetical order (A => 0 up to
+    /// Y => 19), then B, Z, U, O, X and the `*` stop. Case is ignored and any
+    /// other byte is encoded as X.
+    pub static ref AMINO_CONVERSION: [u64; 256] = {
+
+        let mut conversion: [u64; 256] = [24; 256];
+        for (code, residue) in b"ACDEFGHIKLMNPQRSTVWYBZUOX*".iter().enumerate() {
+            conversion[usize::from(*residue)] = code as u64;
+            conversion[usize::from(residue.to_ascii_lowercase())] = code as u64;
+        }
+
+        conversion
+    };
+
+    static ref COMPLEMENT: [u8; 256] = {
+
+        // Bytes that aren't nucleotides are left where they are
+        let mut complement: [u8; 256] = [0; 256];
+        for (i, c) in complement.iter_mut().enumerate() {
+            *c = i as u8;
+        }
+
+        for (a, b) in [(b'A', b'T'), (b'C', b'G'), (b'R', b'Y'),
+                       (b'K', b'M'), (b'B', b'V'), (b'D', b'H')].iter() {
+            complement[usize::from(*a)] = *b;
+            complement[usize::from(*b)] = *a;
+            complement[usize::from(a.to_ascii_lowercase())] = b.to_ascii_lowercase();
+            complement[usize::from(b.to_ascii_lowercase())] = a.to_ascii_lowercase();
+        }
+        complement[usize::from(b'U')] = b'A';
+        complement[usize::from(b'u')] = b'a';
+
+        complement
+    };
+
+    static ref NUCLEOTIDE: [bool; 256] = {
+
+        let mut nucleotide: [bool; 256] = [false; 256];
+        for base in b"ACGTURYSWKMBDHVN".iter() {
+            nucleotide[usize::from(*base)] = true;
+            nucleotide[usize::from(base.to_ascii_lowercase())] = true;
+        }
+
+        nucleotide
+    };
+}
+
+/// Which strand a canonical hash came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
+/// Maximum number of bases that fit in a `u64` at 3 bits per base.
+pub const MAX_K: usize = 21;
+
+/// Maximum number of bases that fit in a `u128` at 3 bits per base.
+pub const MAX_K_128: usize = 42;
+
+// Lowest bit of each of the 21 3-bit bases in a u64
+const LOW_BITS: u64 = 0x1249_2492_4924_9249;
+
+/// Pack a k-mer into its 3-bit representation, first base in the most
+/// significant position.
+///
+/// Fails on an empty k-mer or one longer than [`MAX_K`].
+#[inline(always)]
+pub fn kmerhash(kmer: &[u8]) -> Result<u64, KmerError> {
+    error::check_k(kmer.len(), MAX_K)?;
+    Ok(kmerhash_unchecked(kmer))
+}
+
+/// [`kmerhash`] that also rejects bytes that aren't nucleotides, rather than
+/// hashing gaps, stops and the like as N.
+///
+/// Fails with [`KmerError::InvalidBase`] on the first byte for which
+/// [`is_nucleotide`] is false.
+#[inline(always)]
+pub fn kmerhash_checked(kmer: &[u8]) -> Result<u64, KmerError> {
+    error::check_k(kmer.len(), MAX_K)?;
+    if let Some(pos) = kmer.iter().position(|base| !is_nucleotide(*base)) {
+        return Err(KmerError::InvalidBase { pos, byte: kmer[pos] });
+    }
+    Ok(kmerhash_unchecked(kmer))
+}
+
+/// [`kmerhash`] without the length check, for callers that have already
+/// validated k.
+///
+/// k-mers longer than [`MAX_K`] lose their leading bases and an empty slice
+/// panics.
+#[inline(always)]
+pub fn kmerhash_unchecked(kmer: &[u8]) -> u64 {
+    let mut bits: u64 = 0;
+    bits = bits.wrapping_add(CONVERSION[usize::from(kmer[0])]);
+    kmer[1..].iter().for_each(|base| {
+        bits <<= 3;
+        bits = bits.wrapping_add(CONVERSION[usize::from(*base)]);
+    });
+    bits
+}
+
+/// Whether a byte is a nucleotide: ACGT, U or an IUPAC ambiguity code, in
+/// either case.
+///
+/// [`kmerhash`] encodes any byte for which this is false as N, so callers
+/// that can't trust their input should check it first.
+#[inline(always)]
+pub fn is_nucleotide(base: u8) -> bool {
+    NUCLEOTIDE[usize::from(base)]
+}
+
+/// Reverse complement a nucleotide sequence, preserving case.
+///
+/// U is complemented to A, IUPAC ambiguity codes are complemented (R <-> Y,
+/// K <-> M, B <-> V, D <-> H; S, W and N are their own complement) and any
+/// byte that isn't a nucleotide is kept as is, so the result hashes to
+/// [`calc_rc`] of the original.
+pub fn reverse_complement(seq: &[u8]) -> Vec<u8> {
+    seq.iter().rev().map(|base| COMPLEMENT[usize::from(*base)]).collect()
+}
+
+/// Reverse complement of a k-mer already encoded with [`kmerhash`], where
+/// `1 <= k <= MAX_K`.
+///
+/// The A, C, G and T codes are bit palindromes, so reversing the word and
+/// inverting it complements each base in place. N comes back out as `011` and
+/// is folded back to `001` so it matches the forward encoding.
+#[inline(always)]
+pub fn calc_rc(k: usize, khash: u64) -> u64 {
+    // khash is a kmer already processed with kmerhash
+    // k is the k in kmer (thus the seq length)
+    let rc = !khash.reverse_bits() >> (64 - (k * 3));
+    let n = rc & (rc >> 1) & !(rc >> 2) & (LOW_BITS >> (63 - (k * 3)));
+    rc ^ (n << 1)
+}
+
+/// Canonical hash of a k-mer: the smaller of the forward and reverse
+/// complement encodings, so both strands of a sequence hash the same.
+///
+/// Fails on an empty k-mer or one longer than [`MAX_K`].
+#[inline(always)]
+pub fn kmerhash_smallest(kmer: &[u8]) -> Result<u64, KmerError> {
+    error::check_k(kmer.len(), MAX_K)?;
+    Ok(kmerhash_smallest_unchecked(kmer))
+}
+
+/// [`kmerhash_smallest`] without the length check.
+#[inline(always)]
+pub fn kmerhash_smallest_unchecked(kmer: &[u8]) -> u64 {
+    let hash = kmerhash_unchecked(kmer);
+    let rc = calc_rc(kmer.len(), hash);
+    min(hash, rc)
+}
+
+/// [`kmerhash`] into a `u128`, for k-mers of up to [`MAX_K_128`] bases.
+#[inline(always)]
+pub fn kmerhash_u128(kmer: &[u8]) -> Result<u128, KmerError> {
+    error::check_k(kmer.len(), MAX_K_128)?;
+    Ok(kmerhash_u128_unchecked(kmer))
+}
+
+/// [`kmerhash_u128`] without the length check.
+#[inline(always)]
+pub fn kmerhash_u128_unchecked(kmer: &[u8]) -> u128 {
+    word::encode(kmer)
+}
+
+/// [`calc_rc`] for k-mers encoded with [`kmerhash_u128`].
+#[inline(always)]
+pub fn calc_rc_u128(k: usize, khash: u128) -> u128 {
+    word::reverse_complement(k, khash)
+}
+
+/// [`kmerhash_smallest`] into a `u128`, for k-mers of up to [`MAX_K_128`]
+/// bases.
+#[inline(always)]
+pub fn kmerhash_smallest_u128(kmer: &[u8]) -> Result<u128, KmerError> {
+    error::check_k(kmer.len(), MAX_K_128)?;
+    Ok(kmerhash_smallest_u128_unchecked(kmer))
+}
+
+/// [`kmerhash_smallest_u128`] without the length check.
+#[inline(always)]
+pub fn kmerhash_smallest_u128_unchecked(kmer: &[u8]) -> u128 {
+    let hash = kmerhash_u128_unchecked(kmer);
+    let rc = calc_rc_u128(kmer.len(), hash);
+    min(hash, rc)
+}
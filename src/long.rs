@@ -0,0 +1,259 @@
+use crate::decode::decode_word;
+use crate::rolling::{Filtered, NPolicy};
+use crate::{KmerError, Strand, CONVERSION, MAX_K, RC_CONVERSION};
+
+/// A 3-bit k-mer of any length, packed [`MAX_K`] bases per `u64`.
+///
+/// The first word holds the first 21 bases encoded exactly as
+/// [`kmerhash`](crate::kmerhash) would, the next word the following 21 and so
+/// on, with the last word holding whatever is left. Two `LongKmer`s of the same
+/// k therefore order the same way their sequences' 3-bit encodings do, which
+/// is what [`canonical`](LongKmer::canonical) relies on.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LongKmer {
+    k: usize,
+    words: Vec<u64>,
+}
+
+impl LongKmer {
+    /// Encode a k-mer of any length. Fails on an empty slice.
+    pub fn from_bytes(kmer: &[u8]) -> Result<LongKmer, KmerError> {
+        if kmer.is_empty() {
+            return Err(KmerError::Empty);
+        }
+
+        let words = kmer
+            .chunks(MAX_K)
+            .map(|chunk| {
+                chunk.iter().fold(0, |bits, base| (bits << 3) | CONVERSION[usize::from(*base)])
+            })
+            .collect();
+
+        Ok(LongKmer { k: kmer.len(), words })
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Decode back into the sequence, see [`decode`](crate::decode).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut kmer = vec![0; self.k];
+        for (i, chunk) in kmer.chunks_mut(MAX_K).enumerate() {
+            decode_word(self.words[i], chunk);
+        }
+        kmer
+    }
+
+    /// Reverse complement, as if [`from_bytes`](LongKmer::from_bytes) had been
+    /// called on the reverse complement sequence.
+    pub fn reverse_complement(&self) -> LongKmer {
+        let mut rc = LongKmer {
+            k: self.k,
+            words: vec![0; self.words.len()],
+        };
+
+        for i in (0..self.k).rev() {
+            let code = self.code(i);
+            let comp = if code == 1 { 1 } else { 7 - code };
+            let word = (self.k - 1 - i) / MAX_K;
+            rc.words[word] = (rc.words[word] << 3) | comp;
+        }
+
+        rc
+    }
+
+    /// The smaller of the k-mer and its reverse complement, and the strand it
+    /// came from.
+    pub fn canonical(&self) -> (LongKmer, Strand) {
+        let rc = self.reverse_complement();
+        if rc < *self {
+            (rc, Strand::Reverse)
+        } else {
+            (self.clone(), Strand::Forward)
+        }
+    }
+
+    // Bases held by word i
+    #[inline(always)]
+    fn word_len(&self, i: usize) -> usize {
+        if i + 1 < self.words.len() {
+            MAX_K
+        } else {
+            self.k - MAX_K * i
+        }
+    }
+
+    #[inline(always)]
+    fn code(&self, i: usize) -> u64 {
+        let word = i / MAX_K;
+        let offset = self.word_len(word) - 1 - (i % MAX_K);
+        (self.words[word] >> (offset * 3)) & 7
+    }
+
+    // Shift a code in at the end, dropping the first base
+    #[inline(always)]
+    fn push_back(&mut self, code: u64) {
+        let n = self.words.len();
+        for i in 0..n {
+            let carry = if i + 1 < n {
+                self.words[i + 1] >> ((self.word_len(i + 1) - 1) * 3)
+            } else {
+                code
+            };
+            let mask = (1 << (self.word_len(i) * 3)) - 1;
+            self.words[i] = ((self.words[i] << 3) | carry) & mask;
+        }
+    }
+
+    // Shift a code in at the start, dropping the last base
+    #[inline(always)]
+    fn push_front(&mut self, code: u64) {
+        for i in (0..self.words.len()).rev() {
+            let carry = if i > 0 { self.words[i - 1] & 7 } else { code };
+            self.words[i] = (self.words[i] >> 3) | (carry << ((self.word_len(i) - 1) * 3));
+        }
+    }
+}
+
+/// Rolling hash for k-mers of any length, see
+/// [`RollingHash`](crate::RollingHash).
+///
+/// Each step costs one shift per word rather than per base.
+/// [`next_kmer`](LongRollingHash::next_kmer) lends out the window it keeps,
+/// so stepping through a sequence allocates once. The `Iterator` impl yields
+/// each window as its own `LongKmer`, which is a clone and an allocation per
+/// base, so prefer `next_kmer` in hot loops, and
+/// [`RollingHash`](crate::RollingHash) or
+/// [`RollingHash128`](crate::RollingHash128) when k allows.
+pub struct LongRollingHash<'a> {
+    seq: &'a [u8],
+    pos: usize,
+    kmer: Option<LongKmer>,
+}
+
+impl<'a> LongRollingHash<'a> {
+    /// Fails if `k` is 0.
+    pub fn new(seq: &'a [u8], k: usize) -> Result<LongRollingHash<'a>, KmerError> {
+        if k == 0 {
+            return Err(KmerError::Empty);
+        }
+
+        Ok(LongRollingHash {
+            seq,
+            pos: k,
+            kmer: None,
+        })
+    }
+
+    /// Apply `policy` to windows holding N or other ambiguous bases.
+    pub fn with_policy(self, policy: NPolicy) -> Filtered<'a, Self> {
+        // Before the first window pos is k, and the whole of it is shifted in
+        // at once
+        let (seq, k, pos) = match self.kmer {
+            None => (self.seq, self.pos, (self.pos - 1).min(self.seq.len())),
+            Some(ref kmer) => (self.seq, kmer.k, self.pos),
+        };
+        Filtered::new(self, seq, k, pos, policy)
+    }
+
+    /// The next window, borrowed rather than cloned, so only the first one
+    /// allocates.
+    pub fn next_kmer(&mut self) -> Option<(usize, &LongKmer)> {
+        match self.kmer {
+            None => {
+                let kmer = LongKmer::from_bytes(self.seq.get(..self.pos)?).ok()?;
+                Some((0, self.kmer.get_or_insert(kmer)))
+            }
+            Some(ref mut kmer) => {
+                let base = *self.seq.get(self.pos)?;
+                kmer.push_back(CONVERSION[usize::from(base)]);
+                self.pos += 1;
+                Some((self.pos - kmer.k, kmer))
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for LongRollingHash<'a> {
+    type Item = (usize, LongKmer);
+
+    fn next(&mut self) -> Option<(usize, LongKmer)> {
+        self.next_kmer().map(|(pos, kmer)| (pos, kmer.clone()))
+    }
+}
+
+/// Canonical rolling hash for k-mers of any length, see
+/// [`CanonicalRollingHash`](crate::CanonicalRollingHash).
+///
+/// As with [`LongRollingHash`],
+/// [`next_kmer`](LongCanonicalRollingHash::next_kmer) borrows each window and
+/// the `Iterator` impl clones it.
+pub struct LongCanonicalRollingHash<'a> {
+    seq: &'a [u8],
+    pos: usize,
+    kmers: Option<(LongKmer, LongKmer)>,
+}
+
+impl<'a> LongCanonicalRollingHash<'a> {
+    /// Fails if `k` is 0.
+    pub fn new(seq: &'a [u8], k: usize) -> Result<LongCanonicalRollingHash<'a>, KmerError> {
+        if k == 0 {
+            return Err(KmerError::Empty);
+        }
+
+        Ok(LongCanonicalRollingHash {
+            seq,
+            pos: k,
+            kmers: None,
+        })
+    }
+
+    /// Apply `policy` to windows holding N or other ambiguous bases.
+    pub fn with_policy(self, policy: NPolicy) -> Filtered<'a, Self> {
+        // As for LongRollingHash
+        let (seq, k, pos) = match self.kmers {
+            None => (self.seq, self.pos, (self.pos - 1).min(self.seq.len())),
+            Some((ref fwd, _)) => (self.seq, fwd.k, self.pos),
+        };
+        Filtered::new(self, seq, k, pos, policy)
+    }
+
+    /// The next window's canonical k-mer, borrowed rather than cloned, so
+    /// only the first window allocates.
+    pub fn next_kmer(&mut self) -> Option<(usize, &LongKmer, Strand)> {
+        let (fwd, rc) = match self.kmers {
+            None => {
+                let fwd = LongKmer::from_bytes(self.seq.get(..self.pos)?).ok()?;
+                let rc = fwd.reverse_complement();
+                self.kmers.get_or_insert((fwd, rc))
+            }
+            Some(ref mut kmers) => {
+                let base = usize::from(*self.seq.get(self.pos)?);
+                kmers.0.push_back(CONVERSION[base]);
+                kmers.1.push_front(RC_CONVERSION[base]);
+                self.pos += 1;
+                kmers
+            }
+        };
+
+        let pos = self.pos - fwd.k;
+        if *rc < *fwd {
+            Some((pos, rc, Strand::Reverse))
+        } else {
+            Some((pos, fwd, Strand::Forward))
+        }
+    }
+}
+
+impl<'a> Iterator for LongCanonicalRollingHash<'a> {
+    type Item = (usize, LongKmer, Strand);
+
+    fn next(&mut self) -> Option<(usize, LongKmer, Strand)> {
+        self.next_kmer().map(|(pos, kmer, strand)| (pos, kmer.clone(), strand))
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::VecDeque;
+
+use crate::{CanonicalRollingHash, KmerError, Strand};
+
+/// Iterator over the (w, k) minimizers of a sequence.
+///
+/// Every window of `w` consecutive k-mers is represented by its smallest
+/// canonical hash, as [`CanonicalRollingHash`] computes it. Yields
+/// `(position, hash, strand)` each time the chosen k-mer changes, so
+/// neighbouring windows that share a minimizer produce it once.
+///
+/// Ties are broken by robust winnowing: the k-mer already chosen is kept for
+/// as long as it stays in the window and ties for smallest, otherwise the
+/// rightmost of the tied k-mers is taken. This keeps low-complexity runs,
+/// where many k-mers tie, from producing a minimizer at every position.
+///
+/// Candidates are kept in a deque of increasing hashes, so each base costs
+/// amortized O(1) whatever w is. Sequences with fewer than `w` k-mers yield
+/// nothing.
+pub struct Minimizers<'a> {
+    kmers: CanonicalRollingHash<'a>,
+    w: usize,
+    candidates: VecDeque<(usize, u64, Strand)>,
+    chosen: Option<(usize, u64)>,
+}
+
+impl<'a> Minimizers<'a> {
+    /// Fails unless `1 <= k <= MAX_K`, or if `w` is 0.
+    pub fn new(seq: &'a [u8], w: usize, k: usize) -> Result<Minimizers<'a>, KmerError> {
+        if w == 0 {
+            return Err(KmerError::Empty);
+        }
+
+        Ok(Minimizers {
+            kmers: CanonicalRollingHash::new(seq, k)?,
+            w,
+            candidates: VecDeque::with_capacity(w.min(seq.len())),
+            chosen: None,
+        })
+    }
+}
+
+impl<'a> Iterator for Minimizers<'a> {
+    type Item = (usize, u64, Strand);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(usize, u64, Strand)> {
+        loop {
+            let (pos, hash, strand) = self.kmers.next()?;
+
+            // Anything at least as large as the new k-mer can never be the
+            // rightmost smallest again
+            while self.candidates.back().is_some_and(|&(_, back, _)| back >= hash) {
+                self.candidates.pop_back();
+            }
+            self.candidates.push_back((pos, hash, strand));
+
+            if pos + 1 < self.w {
+                continue;
+            }
+            let start = pos + 1 - self.w;
+            while self.candidates.front().is_some_and(|&(front, _, _)| front < start) {
+                self.candidates.pop_front();
+            }
+
+            let (min_pos, min_hash, min_strand) = self.candidates[0];
+            match self.chosen {
+                Some((chosen, chosen_hash)) if chosen >= start && chosen_hash == min_hash => continue,
+                _ => {
+                    self.chosen = Some((min_pos, min_hash));
+                    return Some((min_pos, min_hash, min_strand));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{decode, kmerhash, KmerError};
+
+/* The 3-bit packing keeps similar k-mers close together: T is 000, so T-rich
+ * and short k-mers all sit near zero. mix is MurmurHash3's 64-bit finalizer,
+ * which spreads them out. Every step is invertible (xor with a right shift of
+ * at least half the word undoes itself, and the multipliers are odd), so
+ * unmix can run it backwards and nothing is lost.
+ */
+
+const M1: u64 = 0xff51_afd7_ed55_8ccd;
+const M2: u64 = 0xc4ce_b9fe_1a85_ec53;
+
+// Inverses of M1 and M2 modulo 2^64
+const M1_INV: u64 = 0x4f74_430c_22a5_4005;
+const M2_INV: u64 = 0x9cb4_b2f8_1293_37db;
+
+/// Scramble a hash into a well distributed 64-bit value.
+///
+/// A bijection on `u64`, so distinct k-mers still get distinct values and
+/// [`unmix`] gets the hash back. Works on the output of any of the hashing
+/// functions or iterators.
+#[inline(always)]
+pub fn mix(mut khash: u64) -> u64 {
+    khash ^= khash >> 33;
+    khash = khash.wrapping_mul(M1);
+    khash ^= khash >> 33;
+    khash = khash.wrapping_mul(M2);
+    khash ^ (khash >> 33)
+}
+
+/// Inverse of [`mix`].
+#[inline(always)]
+pub fn unmix(mut mixed: u64) -> u64 {
+    mixed ^= mixed >> 33;
+    mixed = mixed.wrapping_mul(M2_INV);
+    mixed ^= mixed >> 33;
+    mixed = mixed.wrapping_mul(M1_INV);
+    mixed ^ (mixed >> 33)
+}
+
+/// [`kmerhash`] followed by [`mix`].
+#[inline(always)]
+pub fn kmerhash_mixed(kmer: &[u8]) -> Result<u64, KmerError> {
+    kmerhash(kmer).map(mix)
+}
+
+/// Turn a value from [`kmerhash_mixed`] back into its k-mer, see
+/// [`decode`].
+pub fn decode_mixed(mixed: u64, k: usize) -> Result<Vec<u8>, KmerError> {
+    decode(unmix(mixed), k)
+}
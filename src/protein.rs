@@ -0,0 +1,132 @@
+use crate::error::check_k;
+use crate::{KmerError, KmerWord, AMINO_CONVERSION};
+
+/// Maximum number of residues that fit in a `u64` at 5 bits per residue.
+pub const MAX_PEPTIDE_K: usize = 12;
+
+/// Maximum number of residues that fit in a `u128` at 5 bits per residue.
+pub const MAX_PEPTIDE_K_128: usize = 25;
+
+#[inline(always)]
+fn encode<W: KmerWord>(peptide: &[u8]) -> W {
+    peptide.iter().fold(W::default(), |bits, residue| {
+        (bits << 5) | W::from_code(AMINO_CONVERSION[usize::from(*residue)])
+    })
+}
+
+/// Pack a peptide into its 5-bit representation, first residue in the most
+/// significant position.
+///
+/// Fails on an empty peptide or one longer than [`MAX_PEPTIDE_K`].
+#[inline(always)]
+pub fn peptidehash(peptide: &[u8]) -> Result<u64, KmerError> {
+    check_k(peptide.len(), MAX_PEPTIDE_K)?;
+    Ok(peptidehash_unchecked(peptide))
+}
+
+/// [`peptidehash`] without the length check, for hot loops that have already
+/// validated their input. Longer peptides lose their first residues.
+#[inline(always)]
+pub fn peptidehash_unchecked(peptide: &[u8]) -> u64 {
+    encode(peptide)
+}
+
+/// [`peptidehash`] into a `u128`, for peptides of up to
+/// [`MAX_PEPTIDE_K_128`] residues.
+#[inline(always)]
+pub fn peptidehash_u128(peptide: &[u8]) -> Result<u128, KmerError> {
+    check_k(peptide.len(), MAX_PEPTIDE_K_128)?;
+    Ok(peptidehash_u128_unchecked(peptide))
+}
+
+/// [`peptidehash_u128`] without the length check.
+#[inline(always)]
+pub fn peptidehash_u128_unchecked(peptide: &[u8]) -> u128 {
+    encode(peptide)
+}
+
+/// Hash any number of peptides, appending the hashes to `hashes` in order.
+///
+/// Fails, leaving `hashes` as it was, if any peptide is empty or longer than
+/// [`MAX_PEPTIDE_K`].
+pub fn peptidehash_batch<K: AsRef<[u8]>>(peptides: &[K], hashes: &mut Vec<u64>) -> Result<(), KmerError> {
+    let start = hashes.len();
+    hashes.resize(start + peptides.len(), 0);
+    let result = peptidehash_batch_into(peptides, &mut hashes[start..]);
+    if result.is_err() {
+        hashes.truncate(start);
+    }
+    result
+}
+
+/// [`peptidehash_batch`] into a preallocated slice, one hash per peptide.
+///
+/// Also fails if `hashes` isn't the same length as `peptides`.
+pub fn peptidehash_batch_into<K: AsRef<[u8]>>(peptides: &[K], hashes: &mut [u64]) -> Result<(), KmerError> {
+    if hashes.len() != peptides.len() {
+        return Err(KmerError::LengthMismatch { expected: peptides.len(), found: hashes.len() });
+    }
+    for peptide in peptides {
+        check_k(peptide.as_ref().len(), MAX_PEPTIDE_K)?;
+    }
+
+    for (peptide, hash) in peptides.iter().zip(hashes.iter_mut()) {
+        *hash = peptidehash_unchecked(peptide.as_ref());
+    }
+
+    Ok(())
+}
+
+/// Iterator over the 5-bit hash of every k-mer in a protein sequence.
+///
+/// Works like [`Rolling`](crate::Rolling): yields `(position, hash)` where
+/// every hash matches [`peptidehash`] of that window. Usually used through
+/// [`PeptideRollingHash`] or [`PeptideRollingHash128`].
+pub struct PeptideRolling<'a, W> {
+    seq: &'a [u8],
+    k: usize,
+    mask: W,
+    pos: usize,
+    hash: W,
+}
+
+/// [`PeptideRolling`] over `u64` words, for k up to 12.
+pub type PeptideRollingHash<'a> = PeptideRolling<'a, u64>;
+
+/// [`PeptideRolling`] over `u128` words, for k up to 25.
+pub type PeptideRollingHash128<'a> = PeptideRolling<'a, u128>;
+
+impl<'a, W: KmerWord> PeptideRolling<'a, W> {
+    /// Fails unless `1 <= k <= W::BITS / 5`.
+    pub fn new(seq: &'a [u8], k: usize) -> Result<PeptideRolling<'a, W>, KmerError> {
+        check_k(k, W::BITS / 5)?;
+
+        let primed = (k - 1).min(seq.len());
+        Ok(PeptideRolling {
+            seq,
+            k,
+            mask: W::ones(k * 5),
+            pos: primed,
+            hash: encode(&seq[..primed]),
+        })
+    }
+}
+
+impl<'a, W: KmerWord> Iterator for PeptideRolling<'a, W> {
+    type Item = (usize, W);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(usize, W)> {
+        let residue = *self.seq.get(self.pos)?;
+        self.hash = ((self.hash << 5) | W::from_code(AMINO_CONVERSION[usize::from(residue)])) & self.mask;
+        self.pos += 1;
+        Some((self.pos - self.k, self.hash))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.seq.len() - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, W: KmerWord> ExactSizeIterator for PeptideRolling<'a, W> {}
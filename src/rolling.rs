@@ -0,0 +1,296 @@
+use std::marker::PhantomData;
+
+use crate::error::check_k;
+use crate::{Encoding, KmerError, KmerWord, Strand, ThreeBit, CONVERSION};
+
+/// What the hashing iterators do with windows that aren't plain A, C, G and T.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NPolicy {
+    /// Hash every window, with N and anything else unknown encoded as N.
+    Hash,
+    /// Skip any window holding a base other than A, C, G or T, ambiguity
+    /// codes included.
+    Skip,
+    /// Skip any window holding an N, so hashing stops at each N run and picks
+    /// up again with the first whole window after it. This only filters, the
+    /// underlying roll carries on through the run. Other ambiguity codes are
+    /// hashed as they are.
+    Split,
+}
+
+impl NPolicy {
+    #[inline(always)]
+    fn breaks(self, base: u8) -> bool {
+        match self {
+            NPolicy::Hash => false,
+            NPolicy::Skip => CONVERSION[usize::from(base)] == 1,
+            NPolicy::Split => base == b'N' || base == b'n',
+        }
+    }
+}
+
+/// A hashing iterator with an [`NPolicy`] applied, made by the iterators'
+/// `with_policy`.
+///
+/// Positions are still offsets into the whole sequence, so windows after a
+/// skipped stretch carry on from where they are rather than from zero.
+///
+/// Soft-masked windows, those holding any lower case base, hash the same as
+/// upper case ones. They can be skipped with
+/// [`skip_soft_masked`](Filtered::skip_soft_masked) or flagged with
+/// [`flag_soft_masked`](Filtered::flag_soft_masked), on top of the policy;
+/// use [`NPolicy::Hash`] to only deal with soft-masking.
+pub struct Filtered<'a, I> {
+    inner: I,
+    seq: &'a [u8],
+    k: usize,
+    policy: NPolicy,
+    skip_soft_masked: bool,
+    pos: usize,
+    clean: usize,
+    unmasked: usize,
+}
+
+// Bases before pos since the last one that matches
+#[inline(always)]
+fn run(seq: &[u8], pos: usize, matches: impl Fn(u8) -> bool) -> usize {
+    seq[..pos].iter().rev().take_while(|base| !matches(**base)).count()
+}
+
+impl<'a, I> Filtered<'a, I> {
+    // pos is the next base the inner iterator will shift in, which is k - 1
+    // for a fresh iterator and further along for one that's partly consumed.
+    // clean counts the bases before it since the last one the policy breaks
+    // on and unmasked those since the last lower case one.
+    pub(crate) fn new(inner: I, seq: &'a [u8], k: usize, pos: usize, policy: NPolicy) -> Filtered<'a, I> {
+        Filtered {
+            inner,
+            seq,
+            k,
+            policy,
+            skip_soft_masked: false,
+            pos,
+            clean: run(seq, pos, |base| policy.breaks(base)),
+            unmasked: run(seq, pos, |base| base.is_ascii_lowercase()),
+        }
+    }
+
+    /// Also skip any window holding a lower case base.
+    pub fn skip_soft_masked(mut self) -> Filtered<'a, I> {
+        self.skip_soft_masked = true;
+        self
+    }
+
+    /// Yield `(item, masked)`, where masked says whether the window holds a
+    /// lower case base, rather than skipping soft-masked windows.
+    pub fn flag_soft_masked(mut self) -> Flagged<'a, I> {
+        self.skip_soft_masked = false;
+        Flagged { inner: self }
+    }
+}
+
+impl<'a, I: Iterator> Iterator for Filtered<'a, I> {
+    type Item = I::Item;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<I::Item> {
+        loop {
+            let item = self.inner.next()?;
+            let base = self.seq[self.pos];
+            self.pos += 1;
+
+            self.clean = if self.policy.breaks(base) { 0 } else { self.clean + 1 };
+            self.unmasked = if base.is_ascii_lowercase() { 0 } else { self.unmasked + 1 };
+
+            if self.clean >= self.k && (!self.skip_soft_masked || self.unmasked >= self.k) {
+                return Some(item);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.policy {
+            NPolicy::Hash if !self.skip_soft_masked => self.inner.size_hint(),
+            _ => (0, self.inner.size_hint().1),
+        }
+    }
+}
+
+/// [`Filtered`] with a soft-mask flag alongside each item, made by
+/// [`Filtered::flag_soft_masked`].
+pub struct Flagged<'a, I> {
+    inner: Filtered<'a, I>,
+}
+
+impl<'a, I: Iterator> Iterator for Flagged<'a, I> {
+    type Item = (I::Item, bool);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(I::Item, bool)> {
+        let item = self.inner.next()?;
+        Some((item, self.inner.unmasked < self.inner.k))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator over the 3-bit hash of every k-mer in a sequence.
+///
+/// Each step shifts one base into the previous hash and masks off the oldest,
+/// so a whole chromosome costs one table lookup per base. Yields
+/// `(position, hash)` where position is the offset of the window's first
+/// base, and every hash matches [`kmerhash`](crate::kmerhash) of that window.
+///
+/// Usually used through [`RollingHash`] or [`RollingHash128`]. Other encodings
+/// can be chosen with `E`, in which case k can be up to `W::BITS / E::BITS`.
+pub struct Rolling<'a, W, E = ThreeBit> {
+    seq: &'a [u8],
+    k: usize,
+    mask: W,
+    pos: usize,
+    hash: W,
+    encoding: PhantomData<E>,
+}
+
+/// [`Rolling`] over `u64` words, for k up to 21.
+pub type RollingHash<'a> = Rolling<'a, u64>;
+
+/// [`Rolling`] over `u128` words, for k up to 42.
+pub type RollingHash128<'a> = Rolling<'a, u128>;
+
+impl<'a, W: KmerWord, E: Encoding> Rolling<'a, W, E> {
+    /// Fails unless `1 <= k <= W::BITS / E::BITS`, which is `W::MAX_K` for
+    /// the default encoding.
+    pub fn new(seq: &'a [u8], k: usize) -> Result<Rolling<'a, W, E>, KmerError> {
+        check_k(k, W::BITS / E::BITS)?;
+
+        let mut hash = W::default();
+        let primed = (k - 1).min(seq.len());
+        for base in &seq[..primed] {
+            hash = (hash << E::BITS) | W::from_code(E::code(*base));
+        }
+
+        Ok(Rolling {
+            seq,
+            k,
+            mask: W::ones(k * E::BITS),
+            pos: primed,
+            hash,
+            encoding: PhantomData,
+        })
+    }
+
+    /// Apply `policy` to windows holding N or other ambiguous bases.
+    pub fn with_policy(self, policy: NPolicy) -> Filtered<'a, Self> {
+        let (seq, k, pos) = (self.seq, self.k, self.pos);
+        Filtered::new(self, seq, k, pos, policy)
+    }
+}
+
+impl<'a, W: KmerWord, E: Encoding> Iterator for Rolling<'a, W, E> {
+    type Item = (usize, W);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(usize, W)> {
+        let base = *self.seq.get(self.pos)?;
+        self.hash = ((self.hash << E::BITS) | W::from_code(E::code(base))) & self.mask;
+        self.pos += 1;
+        Some((self.pos - self.k, self.hash))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.seq.len() - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, W: KmerWord, E: Encoding> ExactSizeIterator for Rolling<'a, W, E> {}
+
+/// Iterator over the canonical 3-bit hash of every k-mer in a sequence.
+///
+/// The reverse complement word is kept alongside the forward one, shifting
+/// right and inserting the complemented base at the top, so there's no
+/// per-window [`calc_rc`](crate::calc_rc). Yields `(position, hash, strand)`,
+/// where the hash matches [`kmerhash_smallest`](crate::kmerhash_smallest) and
+/// strand is the one it came from. Palindromes report [`Strand::Forward`].
+///
+/// Usually used through [`CanonicalRollingHash`] or
+/// [`CanonicalRollingHash128`]. Other encodings can be chosen with `E`, as for
+/// [`Rolling`].
+pub struct CanonicalRolling<'a, W, E = ThreeBit> {
+    seq: &'a [u8],
+    k: usize,
+    mask: W,
+    shift: usize,
+    pos: usize,
+    fwd: W,
+    rc: W,
+    encoding: PhantomData<E>,
+}
+
+/// [`CanonicalRolling`] over `u64` words, for k up to 21.
+pub type CanonicalRollingHash<'a> = CanonicalRolling<'a, u64>;
+
+/// [`CanonicalRolling`] over `u128` words, for k up to 42.
+pub type CanonicalRollingHash128<'a> = CanonicalRolling<'a, u128>;
+
+impl<'a, W: KmerWord, E: Encoding> CanonicalRolling<'a, W, E> {
+    /// Fails unless `1 <= k <= W::BITS / E::BITS`, which is `W::MAX_K` for
+    /// the default encoding.
+    pub fn new(seq: &'a [u8], k: usize) -> Result<CanonicalRolling<'a, W, E>, KmerError> {
+        check_k(k, W::BITS / E::BITS)?;
+
+        let shift = (k - 1) * E::BITS;
+        let mut fwd = W::default();
+        let mut rc = W::default();
+        let primed = (k - 1).min(seq.len());
+        for base in &seq[..primed] {
+            fwd = (fwd << E::BITS) | W::from_code(E::code(*base));
+            rc = (rc >> E::BITS) | (W::from_code(E::rc_code(*base)) << shift);
+        }
+
+        Ok(CanonicalRolling {
+            seq,
+            k,
+            mask: W::ones(k * E::BITS),
+            shift,
+            pos: primed,
+            fwd,
+            rc,
+            encoding: PhantomData,
+        })
+    }
+
+    /// Apply `policy` to windows holding N or other ambiguous bases.
+    pub fn with_policy(self, policy: NPolicy) -> Filtered<'a, Self> {
+        let (seq, k, pos) = (self.seq, self.k, self.pos);
+        Filtered::new(self, seq, k, pos, policy)
+    }
+}
+
+impl<'a, W: KmerWord, E: Encoding> Iterator for CanonicalRolling<'a, W, E> {
+    type Item = (usize, W, Strand);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(usize, W, Strand)> {
+        let base = *self.seq.get(self.pos)?;
+        self.fwd = ((self.fwd << E::BITS) | W::from_code(E::code(base))) & self.mask;
+        self.rc = (self.rc >> E::BITS) | (W::from_code(E::rc_code(base)) << self.shift);
+        self.pos += 1;
+
+        if self.rc < self.fwd {
+            Some((self.pos - self.k, self.rc, Strand::Reverse))
+        } else {
+            Some((self.pos - self.k, self.fwd, Strand::Forward))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.seq.len() - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, W: KmerWord, E: Encoding> ExactSizeIterator for CanonicalRolling<'a, W, E> {}
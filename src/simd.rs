@@ -0,0 +1,578 @@
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use std::mem;
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+
+use crate::error::check_k;
+use crate::{kmerhash_unchecked, KmerError, KmerWord, CONVERSION, MAX_K};
+
+/// Encode four k-mers at once, with AVX2 when the CPU has it and one at a time
+/// otherwise. Both paths give the same hashes.
+///
+/// Fails unless all four are the same length, between 1 and [`MAX_K`].
+pub fn hash4(kmers: (&[u8], &[u8], &[u8], &[u8])) -> Result<(u64, u64, u64, u64), KmerError> {
+    let expected = kmers.0.len();
+    check_k(expected, MAX_K)?;
+    for found in [kmers.1.len(), kmers.2.len(), kmers.3.len()].iter().cloned() {
+        if found != expected {
+            return Err(KmerError::LengthMismatch { expected, found });
+        }
+    }
+
+    Ok(hash4_unchecked(kmers))
+}
+
+/// [`hash4`] without the length checks. Every k-mer is assumed to be as long
+/// as the first.
+pub fn hash4_unchecked(kmers: (&[u8], &[u8], &[u8], &[u8])) -> (u64, u64, u64, u64) {
+    hash4_with(Level::detect(), kmers)
+}
+
+/// Encode eight k-mers at once, with AVX-512 when the CPU has it and through
+/// [`hash4`] otherwise. Every path gives the same hashes.
+///
+/// Fails unless all eight are the same length, between 1 and [`MAX_K`].
+pub fn hash8(kmers: [&[u8]; 8]) -> Result<[u64; 8], KmerError> {
+    let expected = kmers[0].len();
+    check_k(expected, MAX_K)?;
+    for kmer in kmers[1..].iter() {
+        if kmer.len() != expected {
+            return Err(KmerError::LengthMismatch { expected, found: kmer.len() });
+        }
+    }
+
+    Ok(hash8_unchecked(kmers))
+}
+
+/// [`hash8`] without the length checks. Every k-mer is assumed to be as long
+/// as the first.
+pub fn hash8_unchecked(kmers: [&[u8]; 8]) -> [u64; 8] {
+    hash8_with(Level::detect(), kmers)
+}
+
+/// Hash any number of k-mers, appending the hashes to `hashes` in order.
+///
+/// Runs of eight or four equal length k-mers go through [`hash8`] or
+/// [`hash4`], anything else is hashed one at a time. Fails, leaving `hashes`
+/// as it was, if any k-mer is empty or longer than [`MAX_K`].
+pub fn hash_batch<K: AsRef<[u8]>>(kmers: &[K], hashes: &mut Vec<u64>) -> Result<(), KmerError> {
+    let start = hashes.len();
+    hashes.resize(start + kmers.len(), 0);
+    let result = hash_batch_into(kmers, &mut hashes[start..]);
+    if result.is_err() {
+        hashes.truncate(start);
+    }
+    result
+}
+
+/// [`hash_batch`] into a preallocated slice, one hash per k-mer. Handy for
+/// splitting a batch across threads.
+///
+/// Also fails if `hashes` isn't the same length as `kmers`.
+pub fn hash_batch_into<K: AsRef<[u8]>>(kmers: &[K], hashes: &mut [u64]) -> Result<(), KmerError> {
+    if hashes.len() != kmers.len() {
+        return Err(KmerError::LengthMismatch { expected: kmers.len(), found: hashes.len() });
+    }
+    for kmer in kmers {
+        check_k(kmer.as_ref().len(), MAX_K)?;
+    }
+
+    let level = Level::detect();
+    let done = hash_chunks8(level, kmers, hashes);
+    let (kmers, hashes) = (&kmers[done..], &mut hashes[done..]);
+
+    let chunks = kmers.chunks_exact(4);
+    let remainder = chunks.remainder();
+    let mut out = hashes.chunks_exact_mut(4);
+
+    for (chunk, out) in chunks.zip(&mut out) {
+        if same_len(chunk) {
+            let h = hash4_with(level, (chunk[0].as_ref(), chunk[1].as_ref(), chunk[2].as_ref(), chunk[3].as_ref()));
+            out.copy_from_slice(&[h.0, h.1, h.2, h.3]);
+        } else {
+            hash_each(chunk, out);
+        }
+    }
+
+    hash_each(remainder, out.into_remainder());
+
+    Ok(())
+}
+
+/// Hash every k-mer of a sequence, appending the hashes to `hashes` in
+/// order.
+///
+/// The output is exactly what [`RollingHash`](crate::RollingHash) yields, but
+/// neighbouring windows are hashed in parallel lanes: four at a time with
+/// AVX2, eight with AVX-512. Sequences shorter than k add nothing. Fails unless
+/// `1 <= k <= MAX_K`, leaving `hashes` as it was.
+pub fn hash_sequence(seq: &[u8], k: usize, hashes: &mut Vec<u64>) -> Result<(), KmerError> {
+    check_k(k, MAX_K)?;
+    let start = hashes.len();
+    hashes.resize(start + (seq.len() + 1).saturating_sub(k), 0);
+    hash_sequence_into(seq, k, &mut hashes[start..])
+}
+
+/// [`hash_sequence`] into a preallocated slice, which must have room for
+/// exactly one hash per window.
+pub fn hash_sequence_into(seq: &[u8], k: usize, hashes: &mut [u64]) -> Result<(), KmerError> {
+    check_k(k, MAX_K)?;
+    let windows = (seq.len() + 1).saturating_sub(k);
+    if hashes.len() != windows {
+        return Err(KmerError::LengthMismatch { expected: windows, found: hashes.len() });
+    }
+    if windows == 0 {
+        return Ok(());
+    }
+
+    hash_sequence_with(Level::detect(), seq, k, hashes);
+    Ok(())
+}
+
+// Windows hashed per block of codes, which keeps the scratch space for codes
+// on the stack however long the sequence is
+const BLOCK: usize = 4096;
+
+// hash_sequence_into at a given level, which the CPU must support. Lengths
+// are assumed to be checked, with at least one window.
+//
+// Each block's codes start with the last k - 1 codes of the block before, so
+// its first window picks up where the previous block left off.
+pub(crate) fn hash_sequence_with(level: Level, seq: &[u8], k: usize, hashes: &mut [u64]) {
+    let carry = k - 1;
+    let mut codes = [0; BLOCK + MAX_K - 1];
+    codes_with(level, &seq[..carry], &mut codes[..carry]);
+
+    for (block, out) in hashes.chunks_mut(BLOCK).enumerate() {
+        let start = carry + block * BLOCK;
+        let len = out.len();
+        codes_with(level, &seq[start..start + len], &mut codes[carry..carry + len]);
+
+        let codes_block = &codes[..carry + len];
+        match level {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Level::Avx512 => unsafe { hash_sequence_avx512(codes_block, k, out) },
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Level::Avx2 => unsafe { hash_sequence_avx2(codes_block, k, out) },
+            Level::Scalar => roll_codes(codes_block, k, out, 0),
+        }
+
+        codes.copy_within(len..len + carry, 0);
+    }
+}
+
+#[inline(always)]
+fn codes_with(level: Level, seq: &[u8], codes: &mut [u8]) {
+    match level {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Avx2 | Level::Avx512 => unsafe { codes_avx2_seq(seq, codes) },
+        Level::Scalar => {
+            for (base, code) in seq.iter().zip(codes.iter_mut()) {
+                *code = CONVERSION[usize::from(*base)] as u8;
+            }
+        }
+    }
+}
+
+// Fill hashes[from..] with the rolling recurrence, starting from the hash
+// already at hashes[from - 1] (or from scratch when from is 0)
+#[inline(always)]
+fn roll_codes(codes: &[u8], k: usize, hashes: &mut [u64], from: usize) {
+    let mask = u64::mask(k);
+    let mut hash = if from == 0 {
+        codes[..k - 1].iter().fold(0, |hash, code| (hash << 3) | u64::from(*code))
+    } else {
+        hashes[from - 1]
+    };
+
+    for (pos, out) in hashes.iter_mut().enumerate().skip(from) {
+        hash = ((hash << 3) | u64::from(codes[pos + k - 1])) & mask;
+        *out = hash;
+    }
+}
+
+// Hashes as many k-mers as fill whole sets of eight lanes, if the CPU can,
+// and returns how many that was
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn hash_chunks8<K: AsRef<[u8]>>(level: Level, kmers: &[K], hashes: &mut [u64]) -> usize {
+    if let Level::Avx512 = level {
+        let chunks = kmers.chunks_exact(8);
+        let done = kmers.len() - chunks.remainder().len();
+
+        for (chunk, out) in chunks.zip(hashes.chunks_exact_mut(8)) {
+            if same_len(chunk) {
+                let mut lanes: [&[u8]; 8] = [&[]; 8];
+                for (lane, kmer) in lanes.iter_mut().zip(chunk.iter()) {
+                    *lane = kmer.as_ref();
+                }
+                out.copy_from_slice(&hash8_with(level, lanes));
+            } else {
+                hash_each(chunk, out);
+            }
+        }
+
+        done
+    } else {
+        0
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn hash_chunks8<K: AsRef<[u8]>>(_level: Level, _kmers: &[K], _hashes: &mut [u64]) -> usize {
+    0
+}
+
+#[inline(always)]
+fn same_len<K: AsRef<[u8]>>(kmers: &[K]) -> bool {
+    let k = kmers[0].as_ref().len();
+    kmers[1..].iter().all(|kmer| kmer.as_ref().len() == k)
+}
+
+#[inline(always)]
+fn hash_each<K: AsRef<[u8]>>(kmers: &[K], hashes: &mut [u64]) {
+    for (kmer, hash) in kmers.iter().zip(hashes.iter_mut()) {
+        *hash = kmerhash_unchecked(kmer.as_ref());
+    }
+}
+
+// Best instruction set the CPU supports, checked once per call
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Level {
+    Scalar,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Avx2,
+    // AVX-512F and BW, the byte shuffles need both. Also has AVX2, for
+    // whatever doesn't fill all eight lanes
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Avx512,
+}
+
+impl Level {
+    #[inline(always)]
+    fn detect() -> Level {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                if is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512bw") {
+                    return Level::Avx512;
+                }
+                return Level::Avx2;
+            }
+        }
+
+        Level::Scalar
+    }
+}
+
+#[inline(always)]
+fn hash4_with(level: Level, kmers: (&[u8], &[u8], &[u8], &[u8])) -> (u64, u64, u64, u64) {
+    match level {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Level::Avx2 | Level::Avx512 => unsafe { hash4_avx2(kmers) },
+        Level::Scalar => hash4_scalar(kmers),
+    }
+}
+
+#[inline(always)]
+fn hash8_with(level: Level, kmers: [&[u8]; 8]) -> [u64; 8] {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if let Level::Avx512 = level {
+            return unsafe { hash8_avx512(kmers) };
+        }
+    }
+
+    let low = hash4_with(level, (kmers[0], kmers[1], kmers[2], kmers[3]));
+    let high = hash4_with(level, (kmers[4], kmers[5], kmers[6], kmers[7]));
+    [low.0, low.1, low.2, low.3, high.0, high.1, high.2, high.3]
+}
+
+#[inline(always)]
+fn hash4_scalar(kmers: (&[u8], &[u8], &[u8], &[u8])) -> (u64, u64, u64, u64) {
+    (kmerhash_unchecked(kmers.0),
+     kmerhash_unchecked(kmers.1),
+     kmerhash_unchecked(kmers.2),
+     kmerhash_unchecked(kmers.3))
+}
+
+/* Four k-mers at once, one per 64-bit lane, with a table lookup per 32 bytes
+ * rather than per base:
+ *
+ * 1. Each k-mer is copied into its own 24 byte slot of a buffer of T (code
+ *    000) so that it ends at the end of the slot, which leaves the padding
+ *    contributing nothing to the hash.
+ * 2. Three gathers pick up bytes 0-7, 8-15 and 16-23 of every slot, lane l
+ *    holding k-mer l.
+ * 3. codes_avx2 maps all 32 bytes of a register to their 3-bit codes at once.
+ * 4. fold_avx2 packs each lane's eight codes into its low 24 bits.
+ * 5. The three registers are shifted together lane by lane. Bytes 0-2 are
+ *    always padding, so nothing is lost when the first register's top bits
+ *    are shifted out.
+ */
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn hash4_avx2(kmers: (&[u8], &[u8], &[u8], &[u8])) -> (u64, u64, u64, u64) {
+    let lanes = [kmers.0, kmers.1, kmers.2, kmers.3];
+    if lanes.iter().any(|kmer| kmer.is_empty() || kmer.len() > MAX_K) {
+        return hash4_scalar(kmers);
+    }
+
+    let mut buf = [b'T'; 4 * 24];
+    for (slot, kmer) in buf.chunks_exact_mut(24).zip(lanes.iter()) {
+        slot[24 - kmer.len()..].copy_from_slice(kmer);
+    }
+
+    let words = buf.as_ptr() as *const i64;
+    let octets = |first: i64| {
+        let offsets = _mm256_setr_epi64x(first, first + 3, first + 6, first + 9);
+        fold_avx2(codes_avx2(_mm256_i64gather_epi64::<8>(words, offsets)))
+    };
+    let high = _mm256_or_si256(_mm256_slli_epi64::<48>(octets(0)), _mm256_slli_epi64::<24>(octets(1)));
+    let hashes = _mm256_or_si256(high, octets(2));
+
+    let hashes: [u64; 4] = mem::transmute(hashes);
+    (hashes[0], hashes[1], hashes[2], hashes[3])
+}
+
+// Folds neighbouring codes together with maddubs / madd / mul_epu32, 2 bases
+// per 16-bit lane, then 4 per 32-bit lane, then 8 per 64-bit lane, earlier
+// bytes ending up higher
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn fold_avx2(codes: __m256i) -> __m256i {
+    let pairs = _mm256_maddubs_epi16(codes, _mm256_set1_epi16(0x0108));
+    let quads = _mm256_madd_epi16(pairs, _mm256_set1_epi32(0x0001_0040));
+    _mm256_add_epi64(_mm256_mul_epu32(quads, _mm256_set1_epi64x(1 << 12)), _mm256_srli_epi64::<32>(quads))
+}
+
+// 32 bytes to their 3-bit codes, matching CONVERSION.
+//
+// A, C, G, T and U have distinct low nibbles (1, 3, 7, 4 and 5), so one
+// shuffle looks up the code a byte would have and a second looks up the upper
+// case base that nibble belongs to. Bytes that don't match that base are N.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn codes_avx2(bytes: __m256i) -> __m256i {
+    let code_lut = _mm256_setr_epi8(1, 7, 1, 5, 0, 0, 1, 2, 1, 1, 1, 1, 1, 1, 1, 1,
+                                    1, 7, 1, 5, 0, 0, 1, 2, 1, 1, 1, 1, 1, 1, 1, 1);
+    let base_lut = _mm256_setr_epi8(0, 0x41, 0, 0x43, 0x54, 0x55, 0, 0x47, 0, 0, 0, 0, 0, 0, 0, 0,
+                                    0, 0x41, 0, 0x43, 0x54, 0x55, 0, 0x47, 0, 0, 0, 0, 0, 0, 0, 0);
+
+    let upper = _mm256_and_si256(bytes, _mm256_set1_epi8(0xDF_u8 as i8));
+    let nibbles = _mm256_and_si256(upper, _mm256_set1_epi8(0x0F));
+    let matched = _mm256_cmpeq_epi8(upper, _mm256_shuffle_epi8(base_lut, nibbles));
+
+    _mm256_blendv_epi8(_mm256_set1_epi8(1), _mm256_shuffle_epi8(code_lut, nibbles), matched)
+}
+
+// As hash4_avx2, with eight 24 byte slots and a lane for each
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn hash8_avx512(kmers: [&[u8]; 8]) -> [u64; 8] {
+    if kmers.iter().any(|kmer| kmer.is_empty() || kmer.len() > MAX_K) {
+        let mut hashes = [0; 8];
+        hash_each(&kmers, &mut hashes);
+        return hashes;
+    }
+
+    let mut buf = [b'T'; 8 * 24];
+    for (slot, kmer) in buf.chunks_exact_mut(24).zip(kmers.iter()) {
+        slot[24 - kmer.len()..].copy_from_slice(kmer);
+    }
+
+    let words = buf.as_ptr() as *const i64;
+    let octets = |first: i64| {
+        let offsets = _mm512_setr_epi64(first, first + 3, first + 6, first + 9,
+                                        first + 12, first + 15, first + 18, first + 21);
+        fold_avx512(codes_avx512(_mm512_i64gather_epi64::<8>(offsets, words)))
+    };
+    let high = _mm512_or_si512(_mm512_slli_epi64::<48>(octets(0)), _mm512_slli_epi64::<24>(octets(1)));
+
+    mem::transmute(_mm512_or_si512(high, octets(2)))
+}
+
+// codes_avx2 over 64 bytes. The shuffles look up within each 128-bit lane, so
+// the tables are just repeated four times.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx512f,avx512bw")]
+#[inline]
+unsafe fn codes_avx512(bytes: __m512i) -> __m512i {
+    let code_lut = _mm512_broadcast_i32x4(_mm_setr_epi8(1, 7, 1, 5, 0, 0, 1, 2, 1, 1, 1, 1, 1, 1, 1, 1));
+    let base_lut = _mm512_broadcast_i32x4(_mm_setr_epi8(0, 0x41, 0, 0x43, 0x54, 0x55, 0, 0x47, 0, 0, 0, 0, 0, 0, 0, 0));
+
+    let upper = _mm512_and_si512(bytes, _mm512_set1_epi8(0xDF_u8 as i8));
+    let nibbles = _mm512_and_si512(upper, _mm512_set1_epi8(0x0F));
+    let matched = _mm512_cmpeq_epi8_mask(upper, _mm512_shuffle_epi8(base_lut, nibbles));
+
+    _mm512_mask_blend_epi8(matched, _mm512_set1_epi8(1), _mm512_shuffle_epi8(code_lut, nibbles))
+}
+
+// As fold_avx2
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx512f,avx512bw")]
+#[inline]
+unsafe fn fold_avx512(codes: __m512i) -> __m512i {
+    let pairs = _mm512_maddubs_epi16(codes, _mm512_set1_epi16(0x0108));
+    let quads = _mm512_madd_epi16(pairs, _mm512_set1_epi32(0x0001_0040));
+    _mm512_add_epi64(_mm512_mul_epu32(quads, _mm512_set1_epi64(1 << 12)), _mm512_srli_epi64::<32>(quads))
+}
+
+// Bases to codes, 32 bytes at a time. codes must be as long as seq.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn codes_avx2_seq(seq: &[u8], codes: &mut [u8]) {
+    let chunks = seq.chunks_exact(32);
+    let remainder = chunks.remainder();
+    let mut out = codes.chunks_exact_mut(32);
+
+    for (chunk, out) in chunks.zip(&mut out) {
+        let bytes = _mm256_loadu_si256(chunk.as_ptr() as *const __m256i);
+        _mm256_storeu_si256(out.as_mut_ptr() as *mut __m256i, codes_avx2(bytes));
+    }
+    for (base, code) in remainder.iter().zip(out.into_remainder()) {
+        *code = CONVERSION[usize::from(*base)] as u8;
+    }
+}
+
+/* Lane l holds the hash at position i + l. Stepping a lane forward four
+ * windows shifts out four bases and shifts in the 12 bits of the four codes
+ * that follow the window, so a single shift / or / and moves all four lanes
+ * from positions i..i+4 to i+4..i+8. The codes for every lane are read with
+ * four overlapping loads, each widened to one code per 64-bit lane.
+ */
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn hash_sequence_avx2(codes: &[u8], k: usize, hashes: &mut [u64]) {
+    let n = hashes.len();
+    if n < 8 {
+        return roll_codes(codes, k, hashes, 0);
+    }
+
+    roll_codes(codes, k, &mut hashes[..4], 0);
+    let mask = _mm256_set1_epi64x(u64::mask(k) as i64);
+    let mut lanes = _mm256_loadu_si256(hashes.as_ptr() as *const __m256i);
+
+    let widen = |offset: usize| {
+        _mm256_cvtepu8_epi64(_mm_loadu_si32(codes.as_ptr().add(offset) as *const _))
+    };
+
+    let mut i = 0;
+    while i + 8 <= n {
+        let base = i + k;
+        let gram = _mm256_or_si256(
+            _mm256_or_si256(_mm256_slli_epi64::<9>(widen(base)), _mm256_slli_epi64::<6>(widen(base + 1))),
+            _mm256_or_si256(_mm256_slli_epi64::<3>(widen(base + 2)), widen(base + 3)));
+        lanes = _mm256_and_si256(_mm256_or_si256(_mm256_slli_epi64::<12>(lanes), gram), mask);
+        _mm256_storeu_si256(hashes.as_mut_ptr().add(i + 4) as *mut __m256i, lanes);
+        i += 4;
+    }
+
+    roll_codes(codes, k, hashes, i + 4);
+}
+
+// As hash_sequence_avx2, eight windows per step and 24 bits per gram
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx512f")]
+unsafe fn hash_sequence_avx512(codes: &[u8], k: usize, hashes: &mut [u64]) {
+    let n = hashes.len();
+    if n < 16 {
+        return roll_codes(codes, k, hashes, 0);
+    }
+
+    roll_codes(codes, k, &mut hashes[..8], 0);
+    let mask = _mm512_set1_epi64(u64::mask(k) as i64);
+    let mut lanes = _mm512_loadu_si512(hashes.as_ptr() as *const _);
+
+    let widen = |offset: usize| {
+        _mm512_cvtepu8_epi64(_mm_loadl_epi64(codes.as_ptr().add(offset) as *const __m128i))
+    };
+
+    let mut i = 0;
+    while i + 16 <= n {
+        let base = i + k;
+        let mut gram = _mm512_setzero_si512();
+        for offset in 0..8 {
+            gram = _mm512_or_si512(_mm512_slli_epi64::<3>(gram), widen(base + offset));
+        }
+        lanes = _mm512_and_si512(_mm512_or_si512(_mm512_slli_epi64::<24>(lanes), gram), mask);
+        _mm512_storeu_si512(hashes.as_mut_ptr().add(i + 8) as *mut _, lanes);
+        i += 8;
+    }
+
+    roll_codes(codes, k, hashes, i + 8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RollingHash;
+
+    // Every level this CPU can run
+    fn levels() -> Vec<Level> {
+        #[allow(unused_mut)]
+        let mut levels = vec![Level::Scalar];
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                levels.push(Level::Avx2);
+                if is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512bw") {
+                    levels.push(Level::Avx512);
+                }
+            }
+        }
+        levels
+    }
+
+    // Long enough to cross a couple of block boundaries, with N, lower case
+    // and junk bytes scattered through it
+    fn sequence(len: usize) -> Vec<u8> {
+        let bases = b"ACGTACGTACGTNacgtuRU-";
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        (0..len).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            bases[(state % bases.len() as u64) as usize]
+        }).collect()
+    }
+
+    #[test]
+    fn every_level_hashes_lanes_like_kmerhash() {
+        let seq = sequence(300);
+        for level in levels() {
+            for k in 1..=MAX_K {
+                let kmers: Vec<&[u8]> = seq.windows(k).collect();
+                for w in kmers.windows(8) {
+                    let expected: Vec<u64> = w.iter().map(|kmer| kmerhash_unchecked(kmer)).collect();
+                    let four = hash4_with(level, (w[0], w[1], w[2], w[3]));
+                    assert_eq!(&[four.0, four.1, four.2, four.3], &expected[..4], "{:?}, k = {}", level, k);
+
+                    let mut lanes: [&[u8]; 8] = [&[]; 8];
+                    lanes.copy_from_slice(w);
+                    assert_eq!(&hash8_with(level, lanes)[..], &expected[..], "{:?}, k = {}", level, k);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_level_matches_rolling() {
+        let seq = sequence(2 * BLOCK + 77);
+        for level in levels() {
+            for len in [1, 7, 21, 40, 100, BLOCK, BLOCK + 20, seq.len()].iter().cloned() {
+                let seq = &seq[..len];
+                for k in 1..=len.min(MAX_K) {
+                    let expected: Vec<u64> = RollingHash::new(seq, k).unwrap().map(|(_, hash)| hash).collect();
+                    let mut hashes = vec![0; expected.len()];
+                    hash_sequence_with(level, seq, k, &mut hashes);
+                    assert!(hashes == expected, "{:?}, length {}, k = {}", level, len, k);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::cmp::min;
+
+use crate::error::check_k;
+use crate::iupac::{ambiguity_mask, is_ambiguous};
+use crate::{AmbiguousRolling, Encoding, KmerError, Strand, TwoBit};
+
+/* 2-bit mode packs 32 bases into a u64 by leaving N out of the alphabet.
+ * Anything that isn't ACGT is encoded as A (00), so on its own the hash can't
+ * tell ANA from AAA. Each hash therefore comes with an N mask, one bit per
+ * base laid out like the hash, set wherever the base wasn't ACGT. A k-mer is
+ * exact when its mask is zero.
+ *
+ * N is A on the reverse strand too, rather than the T that complementing its
+ * code would give, so the reverse strand is always encoded from the bases.
+ */
+
+// Reverse of the low k bits of an N mask, for the other strand
+#[inline(always)]
+fn reverse_mask(k: usize, mask: u32) -> u32 {
+    mask.reverse_bits() >> (32 - k)
+}
+
+/// Pack a k-mer of up to 32 bases two bits per base, along with its N mask.
+///
+/// Fails on an empty k-mer or one longer than 32 bases.
+pub fn kmerhash_2bit(kmer: &[u8]) -> Result<(u64, u32), KmerError> {
+    Ok((TwoBit::hash(kmer)?, ambiguity_mask(kmer)))
+}
+
+/// The smaller of the 2-bit hashes of a k-mer and its reverse complement,
+/// with the N mask laid out for the strand the hash came from.
+pub fn kmerhash_smallest_2bit(kmer: &[u8]) -> Result<(u64, u32, Strand), KmerError> {
+    let (khash, mask) = kmerhash_2bit(kmer)?;
+    let rc = kmer.iter().rev().fold(0, |rc, base| (rc << 2) | TwoBit::rc_code(*base));
+    if rc < khash {
+        Ok((rc, reverse_mask(kmer.len(), mask), Strand::Reverse))
+    } else {
+        Ok((khash, mask, Strand::Forward))
+    }
+}
+
+/// Rolling 2-bit hash for k up to 32, yielding `(position, hash, mask)` as
+/// [`kmerhash_2bit`] would give them.
+pub type TwoBitRollingHash<'a> = AmbiguousRolling<'a, TwoBit>;
+
+/// Canonical rolling 2-bit hash for k up to 32.
+///
+/// Yields `(position, hash, mask, strand)` as [`kmerhash_smallest_2bit`]
+/// would give them.
+pub struct CanonicalTwoBitRollingHash<'a> {
+    seq: &'a [u8],
+    k: usize,
+    mask: u64,
+    shift: usize,
+    pos: usize,
+    fwd: u64,
+    rc: u64,
+    ambiguous: u32,
+}
+
+impl<'a> CanonicalTwoBitRollingHash<'a> {
+    /// Fails unless `1 <= k <= 32`.
+    pub fn new(seq: &'a [u8], k: usize) -> Result<CanonicalTwoBitRollingHash<'a>, KmerError> {
+        check_k(k, TwoBit::MAX_K)?;
+
+        let shift = (k - 1) * 2;
+        let mut fwd = 0;
+        let mut rc = 0;
+        let primed = min(k - 1, seq.len());
+        for base in &seq[..primed] {
+            fwd = (fwd << 2) | TwoBit::code(*base);
+            rc = (rc >> 2) | (TwoBit::rc_code(*base) << shift);
+        }
+
+        Ok(CanonicalTwoBitRollingHash {
+            seq,
+            k,
+            mask: !0 >> (64 - k * 2),
+            shift,
+            pos: primed,
+            fwd,
+            rc,
+            ambiguous: ambiguity_mask(&seq[..primed]),
+        })
+    }
+}
+
+impl<'a> Iterator for CanonicalTwoBitRollingHash<'a> {
+    type Item = (usize, u64, u32, Strand);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(usize, u64, u32, Strand)> {
+        let base = *self.seq.get(self.pos)?;
+        self.fwd = ((self.fwd << 2) | TwoBit::code(base)) & self.mask;
+        self.rc = (self.rc >> 2) | (TwoBit::rc_code(base) << self.shift);
+        self.ambiguous = ((self.ambiguous << 1) | is_ambiguous(base) as u32) & (!0 >> (32 - self.k));
+        self.pos += 1;
+
+        let pos = self.pos - self.k;
+        if self.rc < self.fwd {
+            Some((pos, self.rc, reverse_mask(self.k, self.ambiguous), Strand::Reverse))
+        } else {
+            Some((pos, self.fwd, self.ambiguous, Strand::Forward))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.seq.len() - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for CanonicalTwoBitRollingHash<'a> {}
@@ -0,0 +1,132 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+use crate::CONVERSION;
+
+/// An unsigned integer that 3-bit k-mers can be packed into.
+///
+/// Implemented for `u64` (up to 21 bases) and `u128` (up to 42 bases). Longer
+/// k-mers are handled by [`LongKmer`](crate::LongKmer).
+pub trait KmerWord:
+    Copy
+    + Default
+    + Ord
+    + Hash
+    + Debug
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+    + BitOr<Output = Self>
+    + BitAnd<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    /// Width of the word in bits.
+    const BITS: usize;
+
+    /// Longest k-mer that fits in the word.
+    const MAX_K: usize;
+
+    fn from_code(code: u64) -> Self;
+
+    /// Code of the last base in the word.
+    fn low_code(self) -> u64;
+
+    fn reverse_bits(self) -> Self;
+
+    /// Word with the low `3 * k` bits set.
+    fn mask(k: usize) -> Self;
+
+    /// Word with the low `bits` bits set, for any `1 <= bits <= BITS`.
+    fn ones(bits: usize) -> Self;
+
+    /// Word with the lowest bit of each of the first `k` bases set.
+    fn low_bits(k: usize) -> Self;
+}
+
+impl KmerWord for u64 {
+    const BITS: usize = 64;
+    const MAX_K: usize = 21;
+
+    #[inline(always)]
+    fn from_code(code: u64) -> u64 {
+        code
+    }
+
+    #[inline(always)]
+    fn low_code(self) -> u64 {
+        self & 7
+    }
+
+    #[inline(always)]
+    fn reverse_bits(self) -> u64 {
+        u64::reverse_bits(self)
+    }
+
+    #[inline(always)]
+    fn mask(k: usize) -> u64 {
+        (1 << (k * 3)) - 1
+    }
+
+    #[inline(always)]
+    fn ones(bits: usize) -> u64 {
+        !0 >> (64 - bits)
+    }
+
+    #[inline(always)]
+    fn low_bits(k: usize) -> u64 {
+        0x1249_2492_4924_9249 >> (63 - (k * 3))
+    }
+}
+
+impl KmerWord for u128 {
+    const BITS: usize = 128;
+    const MAX_K: usize = 42;
+
+    #[inline(always)]
+    fn from_code(code: u64) -> u128 {
+        u128::from(code)
+    }
+
+    #[inline(always)]
+    fn low_code(self) -> u64 {
+        (self & 7) as u64
+    }
+
+    #[inline(always)]
+    fn reverse_bits(self) -> u128 {
+        u128::reverse_bits(self)
+    }
+
+    #[inline(always)]
+    fn mask(k: usize) -> u128 {
+        (1 << (k * 3)) - 1
+    }
+
+    #[inline(always)]
+    fn ones(bits: usize) -> u128 {
+        !0 >> (128 - bits)
+    }
+
+    #[inline(always)]
+    fn low_bits(k: usize) -> u128 {
+        0x0924_9249_2492_4924_9249_2492_4924_9249 >> (126 - (k * 3))
+    }
+}
+
+#[inline(always)]
+pub(crate) fn encode<W: KmerWord>(kmer: &[u8]) -> W {
+    let mut bits = W::from_code(CONVERSION[usize::from(kmer[0])]);
+    kmer[1..].iter().for_each(|base| {
+        bits = (bits << 3) | W::from_code(CONVERSION[usize::from(*base)]);
+    });
+    bits
+}
+
+// Same trick as calc_rc, for any word width
+#[inline(always)]
+pub(crate) fn reverse_complement<W: KmerWord>(k: usize, khash: W) -> W {
+    let rc = !khash.reverse_bits() >> (W::BITS - (k * 3));
+    let n = rc & (rc >> 1) & !(rc >> 2) & W::low_bits(k);
+    rc ^ (n << 1)
+}
@@ -0,0 +1,69 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::{calc_rc, is_nucleotide, kmerhash, kmerhash_smallest, reverse_complement};
+
+// Small xorshift so the tests don't need rand
+fn random_kmer(state: &mut u64, k: usize, alphabet: &[u8]) -> Vec<u8> {
+    (0..k)
+        .map(|_| {
+            *state ^= *state << 13;
+            *state ^= *state >> 7;
+            *state ^= *state << 17;
+            alphabet[(*state % alphabet.len() as u64) as usize]
+        })
+        .collect()
+}
+
+#[test]
+fn canonical_is_strand_independent() {
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    for k in 1..=21 {
+        for _ in 0..1_000 {
+            let kmer = random_kmer(&mut state, k, b"ACGTNacgtn");
+            let rc = reverse_complement(&kmer);
+            assert_eq!(kmerhash_smallest(&kmer).unwrap(), kmerhash_smallest(&rc).unwrap(), "{}", String::from_utf8_lossy(&kmer));
+        }
+    }
+}
+
+#[test]
+fn calc_rc_matches_hash_of_reverse_complement() {
+    let mut state = 0x9e37_79b9_7f4a_7c15;
+    for k in 1..=21 {
+        for _ in 0..1_000 {
+            let kmer = random_kmer(&mut state, k, b"ACGTN");
+            let rc = reverse_complement(&kmer);
+            assert_eq!(calc_rc(k, kmerhash(&kmer).unwrap()), kmerhash(&rc).unwrap(), "{}", String::from_utf8_lossy(&kmer));
+        }
+    }
+}
+
+#[test]
+fn canonical_picks_smaller_strand() {
+    // T is 000 so the all-T strand always wins
+    assert_eq!(kmerhash_smallest(b"AAAA").unwrap(), kmerhash(b"TTTT").unwrap());
+    assert_eq!(kmerhash_smallest(b"TTTT").unwrap(), 0);
+    assert_eq!(kmerhash_smallest(b"NNNN").unwrap(), kmerhash(b"NNNN").unwrap());
+}
+
+#[test]
+fn non_nucleotide_bytes_round_trip_as_n() {
+    let mut state = 0xd1b5_4a32_d192_ed03;
+    for k in 1..=21 {
+        for _ in 0..1_000 {
+            let kmer = random_kmer(&mut state, k, b"ACGTRYKM-*\n.");
+            let rc = reverse_complement(&kmer);
+            assert_eq!(calc_rc(k, kmerhash(&kmer).unwrap()), kmerhash(&rc).unwrap(), "{:?}", kmer);
+            assert_eq!(kmerhash_smallest(&kmer).unwrap(), kmerhash_smallest(&rc).unwrap(), "{:?}", kmer);
+        }
+    }
+
+    assert_eq!(kmerhash(b"A-C").unwrap(), kmerhash(b"ANC").unwrap());
+    assert_eq!(reverse_complement(b"aR-y*"), b"*r-Yt".to_vec());
+}
+
+#[test]
+fn nucleotides() {
+    assert!(b"ACGTUNacgtunRYSWKMBDHV".iter().all(|b| is_nucleotide(*b)));
+    assert!(b"-*.\n XEFz".iter().all(|b| !is_nucleotide(*b)));
+}
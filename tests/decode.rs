@@ -0,0 +1,42 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCTTAGCATCGATCGA";
+
+#[test]
+fn decode_round_trips() {
+    for k in 1..=21 {
+        for window in SEQ.windows(k) {
+            assert_eq!(decode(kmerhash(window).unwrap(), k).unwrap(), window);
+        }
+    }
+    for k in 1..=42 {
+        for window in SEQ.windows(k) {
+            assert_eq!(decode_u128(kmerhash_u128(window).unwrap(), k).unwrap(), window);
+        }
+    }
+    for k in [1, 21, 22, 50, SEQ.len()].iter().cloned() {
+        for window in SEQ.windows(k) {
+            assert_eq!(LongKmer::from_bytes(window).unwrap().to_bytes(), window);
+        }
+    }
+}
+
+#[test]
+fn decode_normalises_case_and_unknowns() {
+    let hash = kmerhash(b"acgt-Ry").unwrap();
+    assert_eq!(decode(hash, 7).unwrap(), b"ACGTNNN");
+
+    let mut buf = [0; 4];
+    decode_into(kmerhash(b"GATT").unwrap(), &mut buf).unwrap();
+    assert_eq!(&buf, b"GATT");
+}
+
+#[test]
+fn decode_checks_k() {
+    assert_eq!(decode(0, 0), Err(KmerError::Empty));
+    assert_eq!(decode(0, 22), Err(KmerError::TooLong { len: 22, max: 21 }));
+    assert_eq!(decode(0, usize::MAX), Err(KmerError::TooLong { len: usize::MAX, max: 21 }));
+    assert_eq!(decode_into(0, &mut []), Err(KmerError::Empty));
+}
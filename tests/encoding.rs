@@ -0,0 +1,70 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"ACGTTGCAGATTACAGGCTAGCATCGACTTNTACGACTAGCTTAGCATCGATCGAacgtgattacaGGNNCCATATAT";
+
+fn rolling_matches_scalar<E: Encoding>() {
+    for k in 1..=E::MAX_K {
+        let hashes: Vec<(usize, u64)> = Rolling::<u64, E>::new(SEQ, k).unwrap().collect();
+        let canonical: Vec<(usize, u64, Strand)> = CanonicalRolling::<u64, E>::new(SEQ, k).unwrap().collect();
+        assert_eq!(hashes.len(), SEQ.len() - k + 1);
+        for (i, window) in SEQ.windows(k).enumerate() {
+            let khash = E::hash(window).unwrap();
+            let rc = E::hash(&reverse_complement(window)).unwrap();
+            assert_eq!(hashes[i], (i, khash), "k = {}", k);
+            // TwoBit can't tell N from A once hashed
+            if E::BITS == 3 || !window.contains(&b'N') {
+                assert_eq!(E::reverse_complement(k, khash), rc, "k = {}", k);
+            }
+            assert_eq!(canonical[i].1, E::hash_smallest(window).unwrap(), "k = {}", k);
+            assert_eq!(canonical[i].1, E::hash_smallest(&reverse_complement(window)).unwrap(), "k = {}", k);
+        }
+    }
+}
+
+#[test]
+fn two_bit_rolling_matches_scalar() {
+    rolling_matches_scalar::<TwoBit>();
+}
+
+#[test]
+fn three_bit_rolling_matches_scalar() {
+    rolling_matches_scalar::<ThreeBit>();
+}
+
+#[test]
+fn bitvec_rolling_matches_scalar() {
+    rolling_matches_scalar::<ThreeBitBitvec>();
+}
+
+#[test]
+fn codes() {
+    assert_eq!(TwoBit::hash(b"ACGT").unwrap(), 0b00_01_10_11);
+    assert_eq!(TwoBit::hash(b"acgt").unwrap(), 0b00_01_10_11);
+    assert_eq!(ThreeBitBitvec::hash(b"ATCGN").unwrap(), 0b000_011_001_010_111);
+    let khash = ThreeBitBitvec::hash(b"ATCGN").unwrap();
+    assert_eq!(ThreeBitBitvec::reverse_complement(5, khash), ThreeBitBitvec::hash(b"NCGAT").unwrap());
+
+    for kmer in [&b"GATTACA"[..], b"NNACGTN", b"acgtRYn"].iter() {
+        assert_eq!(ThreeBit::hash(kmer), kmerhash(kmer));
+        assert_eq!(ThreeBit::hash_smallest(kmer), kmerhash_smallest(kmer));
+    }
+}
+
+#[test]
+fn max_k_depends_on_encoding() {
+    assert_eq!(TwoBit::MAX_K, 32);
+    assert_eq!(ThreeBit::MAX_K, MAX_K);
+    assert!(TwoBit::hash(&[b'T'; 32]).is_ok());
+    assert_eq!(TwoBit::hash(&[b'T'; 33]), Err(KmerError::TooLong { len: 33, max: 32 }));
+    assert_eq!(TwoBit::hash(&[b'T'; 32]).unwrap(), !0);
+
+    assert!(Rolling::<u64, TwoBit>::new(SEQ, 33).is_err());
+    let wide: Vec<(usize, u128)> = Rolling::<u128, TwoBit>::new(SEQ, 64).unwrap().collect();
+    for (i, window) in SEQ.windows(64).enumerate() {
+        let (high, low) = (TwoBit::hash(&window[..32]).unwrap(), TwoBit::hash(&window[32..]).unwrap());
+        let expected = (u128::from(high) << 64) | u128::from(low);
+        assert_eq!(wide[i], (i, expected));
+    }
+}
@@ -0,0 +1,45 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+#[test]
+fn rejects_empty_and_long_kmers() {
+    assert_eq!(kmerhash(b""), Err(KmerError::Empty));
+    assert_eq!(kmerhash_smallest(b""), Err(KmerError::Empty));
+    assert_eq!(kmerhash(&[b'A'; 22]), Err(KmerError::TooLong { len: 22, max: 21 }));
+    assert!(kmerhash(&[b'A'; 21]).is_ok());
+
+    assert_eq!(kmerhash_u128(&[b'A'; 43]), Err(KmerError::TooLong { len: 43, max: 42 }));
+    assert!(kmerhash_smallest_u128(&[b'A'; 42]).is_ok());
+
+    assert_eq!(LongKmer::from_bytes(b"").err(), Some(KmerError::Empty));
+}
+
+#[test]
+fn rejects_bad_k() {
+    assert!(RollingHash::new(b"ACGT", 0).is_err());
+    assert!(CanonicalRollingHash::new(b"ACGT", 22).is_err());
+    assert!(RollingHash128::new(b"ACGT", 42).is_ok());
+    assert!(LongRollingHash::new(b"ACGT", 0).is_err());
+}
+
+#[test]
+fn hash4_rejects_mismatched_lengths() {
+    let err = hash4((b"ACGT", b"ACGT", b"ACG", b"ACGT"));
+    assert_eq!(err, Err(KmerError::LengthMismatch { expected: 4, found: 3 }));
+    assert_eq!(hash4((b"", b"", b"", b"")), Err(KmerError::Empty));
+    assert_eq!(err.unwrap_err().to_string(), "expected a length of 4, found 3");
+}
+
+#[test]
+fn checked_rejects_non_nucleotides() {
+    assert_eq!(kmerhash_checked(b"ACGTNRYacgtu"), kmerhash(b"ACGTNRYacgtu"));
+    assert_eq!(kmerhash_checked(b"ACG-T"), Err(KmerError::InvalidBase { pos: 3, byte: b'-' }));
+    assert_eq!(kmerhash_checked(b"*"), Err(KmerError::InvalidBase { pos: 0, byte: b'*' }));
+    assert_eq!(kmerhash_checked(b"AC\nGT"), Err(KmerError::InvalidBase { pos: 2, byte: b'\n' }));
+    assert_eq!(kmerhash_checked(&[b'-'; 22]), Err(KmerError::TooLong { len: 22, max: 21 }));
+    assert_eq!(
+        kmerhash_checked(b"AX").unwrap_err().to_string(),
+        "byte 0x58 at position 1 is not a nucleotide"
+    );
+}
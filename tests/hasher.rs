@@ -0,0 +1,58 @@
+extern crate kmer_hasher;
+
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCTTAGCATCGATCGAACGTNACGGTTCAGA";
+
+#[test]
+fn counts_kmers() {
+    for &k in &[5, 21, 31] {
+        let mut counts: HashMap<&[u8], usize, KmerBuildHasher> = HashMap::default();
+        let mut expected: HashMap<&[u8], usize> = HashMap::new();
+        for window in SEQ.windows(k) {
+            *counts.entry(window).or_insert(0) += 1;
+            *expected.entry(window).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), expected.len());
+        for (kmer, count) in &expected {
+            assert_eq!(counts[kmer], *count);
+        }
+    }
+}
+
+#[test]
+fn distinct_kmers_hash_apart() {
+    let build = KmerBuildHasher::default();
+    let kmers: HashSet<&[u8]> = SEQ.windows(11).collect();
+    let hashes: HashSet<u64> = kmers.iter().map(|kmer| build.hash_one(kmer)).collect();
+    assert_eq!(hashes.len(), kmers.len());
+
+    assert_eq!(build.hash_one(&b"GATTACA"[..]), build.hash_one(b"GATTACA".to_vec()));
+    assert_ne!(build.hash_one(&b"AAA"[..]), build.hash_one(&b"AAAA"[..]));
+}
+
+#[test]
+fn identity_passes_u64_through() {
+    let build = IdentityBuildHasher::default();
+    for khash in RollingHash::new(SEQ, 21).unwrap().map(|(_, h)| mix(h)) {
+        assert_eq!(build.hash_one(khash), khash);
+    }
+
+    let mut seen: HashMap<u64, usize, IdentityBuildHasher> = HashMap::default();
+    for (pos, khash) in RollingHash::new(SEQ, 15).unwrap() {
+        seen.entry(mix(khash)).or_insert(pos);
+    }
+    assert_eq!(seen[&kmerhash_mixed(&SEQ[..15]).unwrap()], 0);
+}
+
+#[test]
+fn identity_hasher_uses_every_field() {
+    let build = IdentityBuildHasher::default();
+    assert_eq!(build.hash_one(7usize), 7);
+    assert_ne!(build.hash_one((1u64, 5u64)), build.hash_one((2u64, 5u64)));
+    assert_ne!(build.hash_one((1u64, 5u64)), build.hash_one((5u64, 1u64)));
+    assert_ne!(build.hash_one(&b"ACGT"[..]), build.hash_one(&b"ACGA"[..]));
+}
@@ -0,0 +1,78 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+#[test]
+fn expands_ambiguity_codes() {
+    let expected: Vec<u64> = [b"ACAT", b"ACCT", b"ACGT", b"ACTT"].iter().map(|kmer| kmerhash(*kmer).unwrap()).collect();
+    assert_eq!(expand(b"ACNT", 4).unwrap(), expected);
+
+    let expected: Vec<u64> = [b"AG", b"AT", b"CG", b"CT"].iter().map(|kmer| kmerhash(*kmer).unwrap()).collect();
+    assert_eq!(expand(b"mk", 16).unwrap(), expected);
+
+    assert_eq!(expand(b"GATTACA", 1).unwrap(), vec![kmerhash(b"GATTACA").unwrap()]);
+}
+
+#[test]
+fn every_code_expands_to_what_it_stands_for() {
+    let codes: &[(u8, &[u8])] = &[
+        (b'R', b"AG"), (b'Y', b"CT"), (b'S', b"CG"), (b'W', b"AT"), (b'K', b"GT"), (b'M', b"AC"),
+        (b'B', b"CGT"), (b'D', b"AGT"), (b'H', b"ACT"), (b'V', b"ACG"), (b'N', b"ACGT"),
+    ];
+    for &(code, bases) in codes {
+        let expected: Vec<u64> = bases.iter().map(|base| kmerhash(&[*base]).unwrap()).collect();
+        assert_eq!(expand(&[code], 4).unwrap(), expected, "{}", code as char);
+        assert_eq!(expand(&[code.to_ascii_lowercase()], 4).unwrap(), expected);
+    }
+}
+
+#[test]
+fn caps_expansion() {
+    assert_eq!(expansions(b"NNNNNNNNNN"), 1 << 20);
+    assert_eq!(expand(b"NNR", 31), Err(KmerError::TooAmbiguous { found: 32, max: 31 }));
+    assert_eq!(expand(b"NNR", 32).unwrap().len(), 32);
+    assert_eq!(expand(b"", 32), Err(KmerError::Empty));
+}
+
+#[test]
+fn reports_ambiguous_positions() {
+    assert_eq!(kmerhash_ambiguous(b"ACGT").unwrap(), (kmerhash(b"ACGT").unwrap(), 0));
+    assert_eq!(kmerhash_ambiguous(b"NCGR").unwrap(), (kmerhash(b"NCGR").unwrap(), 0b1001));
+    assert_eq!(kmerhash_ambiguous(b"acyt").unwrap().1, 0b0010);
+}
+
+#[test]
+fn ambiguous_rolling_matches_scalar() {
+    let seq = b"ACGTNACGGTTCAGRYNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACwsKMnacgt";
+    for k in 1..=21 {
+        let hashes: Vec<(usize, u64, u32)> = AmbiguousRollingHash::new(seq, k).unwrap().collect();
+        assert_eq!(hashes.len(), seq.len() - k + 1);
+        for (i, window) in seq.windows(k).enumerate() {
+            let (hash, mask) = kmerhash_ambiguous(window).unwrap();
+            assert_eq!(hashes[i], (i, hash, mask), "k = {}", k);
+        }
+    }
+}
+
+// One bit per base, purine or pyrimidine, so k could go up to 64
+struct Purine;
+
+impl Encoding for Purine {
+    const BITS: usize = 1;
+
+    fn table() -> &'static [u64; 256] {
+        static TABLE: [u64; 256] = [0; 256];
+        &TABLE
+    }
+
+    fn complement(code: u64) -> u64 {
+        code ^ 1
+    }
+}
+
+#[test]
+fn ambiguous_rolling_caps_k_at_mask_width() {
+    let seq = [b'A'; 40];
+    assert_eq!(AmbiguousRolling::<Purine>::new(&seq, 32).unwrap().count(), 9);
+    assert_eq!(AmbiguousRolling::<Purine>::new(&seq, 33).err(), Some(KmerError::TooLong { len: 33, max: 32 }));
+}
@@ -0,0 +1,80 @@
+extern crate kmer_hasher;
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCTTAGCATCGATCGA";
+
+#[test]
+fn round_trips() {
+    for window in SEQ.windows(21) {
+        let kmer = Kmer::<21>::from_bytes(window).unwrap();
+        assert_eq!(kmer.word(), kmerhash(window).unwrap());
+        assert_eq!(kmer.to_bytes(), window.to_vec());
+        assert_eq!(kmer.to_string().as_bytes(), window);
+        assert_eq!(Kmer::<21>::from_hash(kmer.word()).unwrap(), kmer);
+
+        let dynamic = DynKmer::from_bytes(window).unwrap();
+        assert_eq!((dynamic.k(), dynamic.word()), (21, kmer.word()));
+        assert_eq!(dynamic.to_string(), kmer.to_string());
+        assert_eq!(DynKmer::from(kmer), dynamic);
+        assert_eq!(Kmer::<21>::try_from(dynamic), Ok(kmer));
+    }
+    assert_eq!(Kmer::<4>::from_bytes(b"acgu").unwrap().to_string(), "ACGT");
+}
+
+#[test]
+fn checks_k() {
+    assert_eq!(Kmer::<21>::from_bytes(b"ACGT"), Err(KmerError::LengthMismatch { expected: 21, found: 4 }));
+    assert_eq!(Kmer::<22>::from_bytes(&[b'A'; 22]), Err(KmerError::TooLong { len: 22, max: 21 }));
+    assert_eq!(Kmer::<0>::from_hash(0), Err(KmerError::Empty));
+    assert_eq!(DynKmer::from_bytes(b""), Err(KmerError::Empty));
+    assert_eq!(DynKmer::from_hash(0, 22), Err(KmerError::TooLong { len: 22, max: 21 }));
+
+    let dynamic = DynKmer::from_bytes(b"ACGTA").unwrap();
+    assert_eq!(Kmer::<4>::try_from(dynamic), Err(KmerError::LengthMismatch { expected: 4, found: 5 }));
+    assert_eq!(Kmer::<3>::from_hash(u64::MAX).unwrap().to_string(), "AAA");
+}
+
+#[test]
+fn revcomp_and_canonical() {
+    for k in 1..=21 {
+        for window in SEQ.windows(k) {
+            let kmer = DynKmer::from_bytes(window).unwrap();
+            assert_eq!(kmer.revcomp().to_bytes(), reverse_complement(window));
+            assert_eq!(kmer.revcomp().revcomp(), kmer);
+
+            let (canonical, strand) = kmer.canonical();
+            assert_eq!(canonical.word(), kmerhash_smallest(window).unwrap());
+            match strand {
+                Strand::Forward => assert_eq!(canonical, kmer),
+                Strand::Reverse => assert_eq!(canonical, kmer.revcomp()),
+            }
+        }
+    }
+
+    let kmer = Kmer::<7>::from_bytes(b"GATTACA").unwrap();
+    assert_eq!(kmer.revcomp().to_string(), "TGTAATC");
+    assert_eq!(kmer.canonical(), (Kmer::<7>::from_bytes(b"TGTAATC").unwrap(), Strand::Reverse));
+    let palindrome = Kmer::<4>::from_bytes(b"ACGT").unwrap();
+    assert_eq!(palindrome.canonical(), (palindrome, Strand::Forward));
+}
+
+#[test]
+fn ord_and_hash() {
+    let kmers: Vec<Kmer<5>> = SEQ.windows(5).map(|w| Kmer::from_bytes(w).unwrap()).collect();
+    let mut sorted = kmers.clone();
+    sorted.sort();
+    assert!(sorted.windows(2).all(|pair| pair[0].word() <= pair[1].word()));
+
+    let unique: HashSet<Kmer<5>> = kmers.iter().cloned().collect();
+    let windows: HashSet<&[u8]> = SEQ.windows(5).collect();
+    assert_eq!(unique.len(), windows.len());
+
+    let short = DynKmer::from_bytes(b"TTT").unwrap();
+    let long = DynKmer::from_bytes(b"AAAA").unwrap();
+    assert!(short < long);
+    assert_ne!(DynKmer::from_bytes(b"TT").unwrap(), DynKmer::from_bytes(b"TTT").unwrap());
+}
@@ -0,0 +1,116 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCnacgtGATTACAGATTACACCGGTTAAGCTTAGCATCGATCGA";
+
+#[test]
+fn u128_matches_u64_for_short_kmers() {
+    for k in 1..=21 {
+        for window in SEQ.windows(k) {
+            assert_eq!(kmerhash_u128(window).unwrap(), u128::from(kmerhash(window).unwrap()));
+            assert_eq!(kmerhash_smallest_u128(window).unwrap(), u128::from(kmerhash_smallest(window).unwrap()));
+        }
+    }
+}
+
+#[test]
+fn u128_canonical_is_strand_independent() {
+    for k in 1..=42 {
+        for window in SEQ.windows(k) {
+            let rc = reverse_complement(window);
+            assert_eq!(calc_rc_u128(k, kmerhash_u128(window).unwrap()), kmerhash_u128(&rc).unwrap(), "k = {}", k);
+            assert_eq!(kmerhash_smallest_u128(window).unwrap(), kmerhash_smallest_u128(&rc).unwrap());
+        }
+    }
+}
+
+#[test]
+fn u128_rolling_matches_scalar() {
+    for k in [1, 21, 22, 31, 42].iter().cloned() {
+        let hashes: Vec<(usize, u128)> = RollingHash128::new(SEQ, k).unwrap().collect();
+        let canonical: Vec<(usize, u128, Strand)> = CanonicalRollingHash128::new(SEQ, k).unwrap().collect();
+        assert_eq!(hashes.len(), SEQ.len() - k + 1);
+        for (i, window) in SEQ.windows(k).enumerate() {
+            assert_eq!(hashes[i], (i, kmerhash_u128(window).unwrap()));
+            assert_eq!(canonical[i].1, kmerhash_smallest_u128(window).unwrap());
+        }
+    }
+}
+
+#[test]
+fn long_kmers_match_kmerhash() {
+    for k in 1..=64 {
+        for window in SEQ.windows(k) {
+            let long = LongKmer::from_bytes(window).unwrap();
+            let words: Vec<u64> = window.chunks(MAX_K).map(kmerhash_unchecked).collect();
+            assert_eq!(long.words(), &words[..]);
+            assert_eq!(long.reverse_complement(), LongKmer::from_bytes(&reverse_complement(window)).unwrap());
+        }
+    }
+}
+
+#[test]
+fn long_rolling_matches_from_bytes() {
+    for k in [1, 21, 22, 42, 51, 63, 64, 100].iter().cloned() {
+        let hashes: Vec<(usize, LongKmer)> = LongRollingHash::new(SEQ, k).unwrap().collect();
+        let canonical: Vec<(usize, LongKmer, Strand)> = LongCanonicalRollingHash::new(SEQ, k).unwrap().collect();
+        assert_eq!(hashes.len(), SEQ.len() - k + 1);
+        assert_eq!(canonical.len(), SEQ.len() - k + 1);
+        for (i, window) in SEQ.windows(k).enumerate() {
+            let kmer = LongKmer::from_bytes(window).unwrap();
+            assert_eq!(hashes[i], (i, kmer.clone()), "k = {}", k);
+
+            let (canon, strand) = kmer.canonical();
+            assert_eq!(canonical[i], (i, canon.clone(), strand), "k = {}", k);
+            assert_eq!(canon, LongKmer::from_bytes(&reverse_complement(window)).unwrap().canonical().0);
+        }
+    }
+    assert_eq!(LongRollingHash::new(SEQ, SEQ.len() + 1).unwrap().count(), 0);
+}
+
+#[test]
+fn next_kmer_lends_the_same_windows() {
+    for &k in &[1, 21, 22, 50] {
+        let owned: Vec<(usize, LongKmer)> = LongRollingHash::new(SEQ, k).unwrap().collect();
+        let mut hashes = LongRollingHash::new(SEQ, k).unwrap();
+        let mut words = None;
+        for expected in &owned {
+            let (pos, kmer) = hashes.next_kmer().unwrap();
+            assert_eq!((pos, kmer), (expected.0, &expected.1), "k = {}", k);
+            // Always the same buffer, shifted in place
+            assert_eq!(*words.get_or_insert(kmer.words().as_ptr()), kmer.words().as_ptr());
+        }
+        assert!(hashes.next_kmer().is_none());
+
+        let owned: Vec<(usize, LongKmer, Strand)> = LongCanonicalRollingHash::new(SEQ, k).unwrap().collect();
+        let mut canonical = LongCanonicalRollingHash::new(SEQ, k).unwrap();
+        for expected in &owned {
+            let (pos, kmer, strand) = canonical.next_kmer().unwrap();
+            assert_eq!((pos, kmer, strand), (expected.0, &expected.1, expected.2), "k = {}", k);
+        }
+        assert!(canonical.next_kmer().is_none());
+    }
+}
+
+#[test]
+fn long_rolling_skips_windows_with_n() {
+    for &k in &[5, 30, 50] {
+        let expected: Vec<usize> = (0..=SEQ.len() - k)
+            .filter(|&i| !SEQ[i..i + k].iter().any(|&base| base == b'N' || base == b'n'))
+            .collect();
+        let fwd: Vec<usize> =
+            LongRollingHash::new(SEQ, k).unwrap().with_policy(NPolicy::Split).map(|(pos, _)| pos).collect();
+        let canonical: Vec<usize> =
+            LongCanonicalRollingHash::new(SEQ, k).unwrap().with_policy(NPolicy::Skip).map(|(pos, _, _)| pos).collect();
+        assert_eq!(fwd, expected, "k = {}", k);
+        assert_eq!(canonical, expected, "k = {}", k);
+
+        // Policies line up with the bases whenever they're applied
+        let mut hashes = LongRollingHash::new(SEQ, k).unwrap();
+        hashes.nth(2);
+        let rest: Vec<usize> = hashes.with_policy(NPolicy::Split).map(|(pos, _)| pos).collect();
+        assert_eq!(rest, expected.iter().cloned().filter(|&pos| pos > 2).collect::<Vec<_>>(), "k = {}", k);
+    }
+    assert_eq!(LongRollingHash::new(SEQ, SEQ.len() + 1).unwrap().with_policy(NPolicy::Skip).count(), 0);
+}
@@ -0,0 +1,67 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCTTAGCATCGATCGAAAAAAAAAAAAAAAAAAAAAAACACACACACACACAGATTACA";
+
+// Robust winnowing, one window at a time
+fn naive(seq: &[u8], w: usize, k: usize) -> Vec<(usize, u64, Strand)> {
+    let kmers: Vec<(usize, u64, Strand)> = CanonicalRollingHash::new(seq, k).unwrap().collect();
+    let mut chosen: Option<(usize, u64, Strand)> = None;
+    let mut minimizers = Vec::new();
+    for window in kmers.windows(w) {
+        let min = window.iter().map(|(_, hash, _)| *hash).min().unwrap();
+        if let Some((pos, hash, _)) = chosen {
+            if pos >= window[0].0 && hash == min {
+                continue;
+            }
+        }
+        let rightmost = *window.iter().rev().find(|(_, hash, _)| *hash == min).unwrap();
+        chosen = Some(rightmost);
+        minimizers.push(rightmost);
+    }
+    minimizers
+}
+
+#[test]
+fn matches_naive_winnowing() {
+    for k in [1, 3, 5, 11, 15, 21].iter().cloned() {
+        for w in [1, 2, 4, 10, 25].iter().cloned() {
+            let minimizers: Vec<(usize, u64, Strand)> = Minimizers::new(SEQ, w, k).unwrap().collect();
+            assert_eq!(minimizers, naive(SEQ, w, k), "w = {}, k = {}", w, k);
+        }
+    }
+}
+
+#[test]
+fn every_window_has_a_minimizer() {
+    let (w, k) = (10, 15);
+    let positions: Vec<usize> = Minimizers::new(SEQ, w, k).unwrap().map(|(pos, _, _)| pos).collect();
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    for start in 0..=SEQ.len() - k + 1 - w {
+        assert!(positions.iter().any(|&pos| pos >= start && pos < start + w), "window at {}", start);
+    }
+}
+
+#[test]
+fn ties_keep_the_chosen_kmer() {
+    // Every k-mer of a homopolymer ties, so each new choice should only come
+    // when the previous one falls out of the window
+    let seq = [b'A'; 40];
+    let positions: Vec<usize> = Minimizers::new(&seq, 5, 3).unwrap().map(|(pos, _, _)| pos).collect();
+    assert_eq!(positions, vec![4, 9, 14, 19, 24, 29, 34]);
+}
+
+#[test]
+fn short_and_bad_input() {
+    assert_eq!(Minimizers::new(b"ACGTACGT", 5, 5).unwrap().count(), 0);
+    assert_eq!(Minimizers::new(b"ACGTACGT", 4, 5).unwrap().count(), 1);
+    assert!(Minimizers::new(SEQ, 0, 5).is_err());
+    assert!(Minimizers::new(SEQ, 5, 22).is_err());
+}
+
+#[test]
+fn huge_windows_yield_nothing() {
+    assert_eq!(Minimizers::new(SEQ, usize::MAX, 5).unwrap().count(), 0);
+    assert_eq!(Minimizers::new(SEQ, 1 << 40, 5).unwrap().count(), 0);
+}
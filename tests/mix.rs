@@ -0,0 +1,42 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+#[test]
+fn unmix_inverts_mix() {
+    let mut x: u64 = 0x9e37_79b9_7f4a_7c15;
+    for _ in 0..10_000 {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        assert_eq!(unmix(mix(x)), x);
+        assert_eq!(mix(unmix(x)), x);
+    }
+    for x in [0, 1, u64::MAX, 1 << 63].iter() {
+        assert_eq!(unmix(mix(*x)), *x);
+    }
+}
+
+#[test]
+fn mixed_kmers_decode() {
+    for kmer in [&b"GATTACA"[..], b"TTTTTTTTTTTTTTTTTTTTT", b"ACGTNACGT", b"T"].iter() {
+        let mixed = kmerhash_mixed(kmer).unwrap();
+        assert_eq!(unmix(mixed), kmerhash(kmer).unwrap());
+        assert_eq!(decode_mixed(mixed, kmer.len()).unwrap(), kmer.to_vec());
+    }
+    assert_eq!(kmerhash_mixed(b""), Err(KmerError::Empty));
+}
+
+#[test]
+fn spreads_t_rich_kmers() {
+    // Every 5-mer over T and one other base packs into the bottom 15 bits
+    // before mixing, but should fill the whole word afterwards
+    let mut high = 0;
+    for bits in 0..32u32 {
+        let kmer: Vec<u8> = (0..5).map(|i| if bits & (1 << i) != 0 { b'C' } else { b'T' }).collect();
+        let mixed = kmerhash_mixed(&kmer).unwrap();
+        assert!(kmerhash(&kmer).unwrap() < 1 << 15);
+        high += mixed >> 63;
+    }
+    assert!(high > 8 && high < 24, "{} of 32 have the top bit set", high);
+}
@@ -0,0 +1,65 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQAPILSRVGDGTQDNLSGAEKAVQVKVKALPDAQFEVVHSLAKWKRQTLGQHDFSAGEGLYTHMKALRPDEDRLSPLHSVYVDQWDWERVMGDGERQFSTLKSTVEAIWAGIKATEAAVSEEFGLAPFLPDQIHFVHSQELLSRYPDLDAKGRERAIAKDLGAVFLVGIGGKLSDGHRHDVRAPDYDDWUOBZX*";
+
+#[test]
+fn codes() {
+    for (code, residue) in b"ACDEFGHIKLMNPQRSTVWYBZUOX*".iter().enumerate() {
+        assert_eq!(peptidehash(&[*residue]).unwrap(), code as u64);
+        assert_eq!(peptidehash(&[residue.to_ascii_lowercase()]).unwrap(), code as u64);
+    }
+    assert_eq!(peptidehash(b"-").unwrap(), peptidehash(b"X").unwrap());
+    assert_eq!(peptidehash(b"MK").unwrap(), (10 << 5) | 8);
+}
+
+#[test]
+fn fits_12_and_25_residues() {
+    assert!(peptidehash(&[b'*'; 12]).is_ok());
+    assert_eq!(peptidehash(&[b'A'; 13]), Err(KmerError::TooLong { len: 13, max: 12 }));
+    assert_eq!(peptidehash(b""), Err(KmerError::Empty));
+    assert!(peptidehash_u128(&[b'*'; 25]).is_ok());
+    assert_eq!(peptidehash_u128(&[b'A'; 26]), Err(KmerError::TooLong { len: 26, max: 25 }));
+
+    for window in SEQ.windows(12) {
+        assert_eq!(u128::from(peptidehash(window).unwrap()), peptidehash_u128(window).unwrap());
+    }
+}
+
+#[test]
+fn rolling_matches_scalar() {
+    for k in 1..=MAX_PEPTIDE_K {
+        let hashes: Vec<(usize, u64)> = PeptideRollingHash::new(SEQ, k).unwrap().collect();
+        assert_eq!(hashes.len(), SEQ.len() - k + 1);
+        for (i, window) in SEQ.windows(k).enumerate() {
+            assert_eq!(hashes[i], (i, peptidehash(window).unwrap()), "k = {}", k);
+        }
+    }
+    for k in 1..=MAX_PEPTIDE_K_128 {
+        let hashes: Vec<(usize, u128)> = PeptideRollingHash128::new(SEQ, k).unwrap().collect();
+        for (i, window) in SEQ.windows(k).enumerate() {
+            assert_eq!(hashes[i], (i, peptidehash_u128(window).unwrap()), "k = {}", k);
+        }
+    }
+    assert!(PeptideRollingHash::new(SEQ, 13).is_err());
+}
+
+#[test]
+fn batch_matches_scalar() {
+    let peptides: Vec<&[u8]> = SEQ.chunks(7).collect();
+    let mut hashes = vec![42];
+    peptidehash_batch(&peptides, &mut hashes).unwrap();
+    assert_eq!(hashes[0], 42);
+    for (peptide, hash) in peptides.iter().zip(&hashes[1..]) {
+        assert_eq!(*hash, peptidehash(peptide).unwrap());
+    }
+
+    let err = peptidehash_batch(&[&b"MK"[..], &[b'A'; 13]], &mut hashes);
+    assert_eq!(err, Err(KmerError::TooLong { len: 13, max: 12 }));
+    assert_eq!(hashes.len(), peptides.len() + 1);
+    assert_eq!(
+        peptidehash_batch_into(&[b"MK"], &mut [0; 2]),
+        Err(KmerError::LengthMismatch { expected: 1, found: 2 })
+    );
+}
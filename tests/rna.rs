@@ -0,0 +1,59 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+const RNA: &[u8] = b"AUGGCUUCAGAUUACANNCGGUUAAGCUUAGCAUCGAUCGAaugcuuacgacuagcuuaNgcauUUUU";
+
+fn transcript(rna: &[u8]) -> Vec<u8> {
+    rna.iter()
+        .map(|base| match base {
+            b'U' => b'T',
+            b'u' => b't',
+            _ => *base,
+        })
+        .collect()
+}
+
+#[test]
+fn rna_hashes_like_its_dna_transcript() {
+    let dna = transcript(RNA);
+    for k in 1..=21 {
+        for (rna, dna) in RNA.windows(k).zip(dna.windows(k)) {
+            assert_eq!(kmerhash(rna), kmerhash(dna));
+            assert_eq!(kmerhash_smallest(rna), kmerhash_smallest(dna));
+            assert_eq!(kmerhash_u128(rna), kmerhash_u128(dna));
+            assert_eq!(TwoBit::hash(rna), TwoBit::hash(dna));
+            assert_eq!(kmerhash_2bit(rna), kmerhash_2bit(dna));
+        }
+
+        let rolling = |seq| RollingHash::new(seq, k).unwrap().collect::<Vec<_>>();
+        let canonical = |seq| CanonicalRollingHash::new(seq, k).unwrap().collect::<Vec<_>>();
+        assert_eq!(rolling(RNA), rolling(&dna));
+        assert_eq!(canonical(RNA), canonical(&dna));
+
+        let (mut from_rna, mut from_dna) = (Vec::new(), Vec::new());
+        hash_sequence(RNA, k, &mut from_rna).unwrap();
+        hash_sequence(&dna, k, &mut from_dna).unwrap();
+        assert_eq!(from_rna, from_dna);
+    }
+}
+
+#[test]
+fn simd_treats_u_as_t() {
+    let rna: Vec<&[u8]> = RNA.windows(21).collect();
+    let dna = transcript(RNA);
+    let dna: Vec<&[u8]> = dna.windows(21).collect();
+    let (mut from_rna, mut from_dna) = (Vec::new(), Vec::new());
+    hash_batch(&rna, &mut from_rna).unwrap();
+    hash_batch(&dna, &mut from_dna).unwrap();
+    assert_eq!(from_rna, from_dna);
+}
+
+#[test]
+fn u_complements_to_a() {
+    assert_eq!(reverse_complement(b"AUGu"), b"aCAT".to_vec());
+    assert_eq!(calc_rc(4, kmerhash(b"AUGu").unwrap()), kmerhash(b"aCAT").unwrap());
+    assert!(is_nucleotide(b'U') && is_nucleotide(b'u'));
+    assert_eq!(RollingHash::new(b"UUNUU", 2).unwrap().with_policy(NPolicy::Skip).count(), 2);
+    assert_eq!(expand(b"U", 4).unwrap(), vec![kmerhash(b"T").unwrap()]);
+}
@@ -0,0 +1,123 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::{
+    kmerhash, kmerhash_smallest, reverse_complement, CanonicalRollingHash, NPolicy, RollingHash, Strand,
+};
+
+#[test]
+fn rolling_matches_kmerhash() {
+    let seq = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCnacgt";
+    for k in 1..=21 {
+        let hashes: Vec<(usize, u64)> = RollingHash::new(seq, k).unwrap().collect();
+        assert_eq!(hashes.len(), seq.len() - k + 1);
+        for (i, window) in seq.windows(k).enumerate() {
+            assert_eq!(hashes[i], (i, kmerhash(window).unwrap()), "k = {}", k);
+        }
+    }
+}
+
+#[test]
+fn short_sequences_yield_nothing() {
+    assert_eq!(RollingHash::new(b"ACGT", 5).unwrap().count(), 0);
+    assert_eq!(RollingHash::new(b"", 1).unwrap().count(), 0);
+    assert_eq!(RollingHash::new(b"ACGT", 4).unwrap().len(), 1);
+}
+
+#[test]
+fn canonical_rolling_matches_kmerhash_smallest() {
+    let seq = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCnacgt-*R";
+    for k in 1..=21 {
+        let hashes: Vec<(usize, u64, Strand)> = CanonicalRollingHash::new(seq, k).unwrap().collect();
+        assert_eq!(hashes.len(), seq.len() - k + 1);
+        for (i, window) in seq.windows(k).enumerate() {
+            let (pos, hash, strand) = hashes[i];
+            assert_eq!(pos, i);
+            assert_eq!(hash, kmerhash_smallest(window).unwrap(), "k = {}", k);
+            match strand {
+                Strand::Forward => assert_eq!(hash, kmerhash(window).unwrap()),
+                Strand::Reverse => assert_eq!(hash, kmerhash(&reverse_complement(window)).unwrap()),
+            }
+        }
+    }
+}
+
+#[test]
+fn canonical_rolling_is_strand_independent() {
+    let seq = b"GATTACAGATTACANNCCGGTTAAGCT";
+    let rc = reverse_complement(seq);
+    let mut fwd: Vec<u64> = CanonicalRollingHash::new(seq, 11).unwrap().map(|(_, h, _)| h).collect();
+    let rev: Vec<u64> = CanonicalRollingHash::new(&rc, 11).unwrap().map(|(_, h, _)| h).collect();
+    fwd.reverse();
+    assert_eq!(fwd, rev);
+}
+
+#[test]
+fn policies_choose_which_windows_are_hashed() {
+    let seq = b"ACGTNACGGTTCAGANNNNCTAGCRAGGGCATnnCGACTAGCATC";
+    for k in 1..=21 {
+        for &(policy, bad) in &[(NPolicy::Hash, &b""[..]), (NPolicy::Skip, &b"NnR"[..]), (NPolicy::Split, &b"Nn"[..])] {
+            let expected: Vec<(usize, u64)> = seq
+                .windows(k)
+                .enumerate()
+                .filter(|(_, window)| !window.iter().any(|base| bad.contains(base)))
+                .map(|(i, window)| (i, kmerhash(window).unwrap()))
+                .collect();
+            let hashes: Vec<(usize, u64)> = RollingHash::new(seq, k).unwrap().with_policy(policy).collect();
+            assert_eq!(hashes, expected, "k = {}, {:?}", k, policy);
+
+            let canonical: Vec<usize> =
+                CanonicalRollingHash::new(seq, k).unwrap().with_policy(policy).map(|(pos, _, _)| pos).collect();
+            assert_eq!(canonical, expected.iter().map(|(pos, _)| *pos).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn policies_on_short_and_all_n_sequences() {
+    assert_eq!(RollingHash::new(b"NNNNNN", 3).unwrap().with_policy(NPolicy::Skip).count(), 0);
+    assert_eq!(RollingHash::new(b"NNNNNN", 3).unwrap().with_policy(NPolicy::Hash).count(), 4);
+    assert_eq!(RollingHash::new(b"AC", 3).unwrap().with_policy(NPolicy::Split).count(), 0);
+}
+
+#[test]
+fn policies_apply_to_partly_consumed_iterators() {
+    let seq = b"ACGTACGTACGTNNACGT";
+    let mut hashes = RollingHash::new(seq, 3).unwrap();
+    hashes.next();
+    hashes.next();
+    let positions: Vec<usize> = hashes.with_policy(NPolicy::Skip).map(|(pos, _)| pos).collect();
+    assert_eq!(positions, vec![2, 3, 4, 5, 6, 7, 8, 9, 14, 15]);
+
+    let mut canonical = CanonicalRollingHash::new(seq, 3).unwrap();
+    canonical.nth(10);
+    let positions: Vec<usize> = canonical.with_policy(NPolicy::Split).map(|(pos, _, _)| pos).collect();
+    assert_eq!(positions, vec![14, 15]);
+}
+
+#[test]
+fn soft_masked_windows_are_skipped_or_flagged() {
+    let seq = b"ACGTacgtACGGTTCAGANNCTAGCTaggcATCGACTAGCATCgACTnnnACGACTAGC";
+    let masked = |window: &[u8]| window.iter().any(u8::is_ascii_lowercase);
+    for k in 1..=21 {
+        let flagged: Vec<((usize, u64), bool)> =
+            RollingHash::new(seq, k).unwrap().with_policy(NPolicy::Hash).flag_soft_masked().collect();
+        assert_eq!(flagged.len(), seq.len() - k + 1);
+        for (i, window) in seq.windows(k).enumerate() {
+            assert_eq!(flagged[i], ((i, kmerhash(window).unwrap()), masked(window)), "k = {}", k);
+        }
+
+        let expected: Vec<usize> = seq
+            .windows(k)
+            .enumerate()
+            .filter(|(_, window)| !masked(window) && !window.contains(&b'N'))
+            .map(|(i, _)| i)
+            .collect();
+        let skipped: Vec<usize> = CanonicalRollingHash::new(seq, k)
+            .unwrap()
+            .with_policy(NPolicy::Split)
+            .skip_soft_masked()
+            .map(|(pos, _, _)| pos)
+            .collect();
+        assert_eq!(skipped, expected, "k = {}", k);
+    }
+}
@@ -0,0 +1,121 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCTTAGCATCGATCGA";
+
+#[test]
+fn hash4_lanes_match_scalar() {
+    for k in 1..=21 {
+        let windows: Vec<&[u8]> = SEQ.windows(k).collect();
+        for w in windows.windows(4) {
+            let hashes = hash4((w[0], w[1], w[2], w[3])).unwrap();
+            assert_eq!(hashes.0, kmerhash(w[0]).unwrap(), "lane 0, k = {}", k);
+            assert_eq!(hashes.1, kmerhash(w[1]).unwrap(), "lane 1, k = {}", k);
+            assert_eq!(hashes.2, kmerhash(w[2]).unwrap(), "lane 2, k = {}", k);
+            assert_eq!(hashes.3, kmerhash(w[3]).unwrap(), "lane 3, k = {}", k);
+        }
+    }
+}
+
+#[test]
+fn hash_batch_matches_scalar() {
+    for k in 1..=21 {
+        let kmers: Vec<&[u8]> = SEQ.windows(k).collect();
+        let expected: Vec<u64> = kmers.iter().map(|kmer| kmerhash(kmer).unwrap()).collect();
+
+        let mut hashes = vec![42];
+        hash_batch(&kmers, &mut hashes).unwrap();
+        assert_eq!(hashes[0], 42);
+        assert_eq!(&hashes[1..], &expected[..]);
+
+        let mut hashes = vec![0; kmers.len()];
+        hash_batch_into(&kmers, &mut hashes).unwrap();
+        assert_eq!(hashes, expected);
+    }
+}
+
+#[test]
+fn hash_batch_handles_mixed_lengths() {
+    let kmers: Vec<Vec<u8>> = (1..=21).map(|k| SEQ[..k].to_vec()).collect();
+    let mut hashes = Vec::new();
+    hash_batch(&kmers, &mut hashes).unwrap();
+    for (kmer, hash) in kmers.iter().zip(hashes.iter()) {
+        assert_eq!(*hash, kmerhash(kmer).unwrap());
+    }
+}
+
+#[test]
+fn hash_batch_rejects_bad_input() {
+    let mut hashes = vec![7];
+    let kmers: [&[u8]; 5] = [b"ACGT", b"ACGT", b"", b"ACGT", b"ACGT"];
+    assert_eq!(hash_batch(&kmers, &mut hashes), Err(KmerError::Empty));
+    assert_eq!(hashes, vec![7]);
+
+    let mut hashes = [0; 4];
+    assert_eq!(
+        hash_batch_into(&kmers, &mut hashes),
+        Err(KmerError::LengthMismatch { expected: 5, found: 4 })
+    );
+}
+
+#[test]
+fn hash8_lanes_match_scalar() {
+    for k in 1..=21 {
+        let windows: Vec<&[u8]> = SEQ.windows(k).collect();
+        for w in windows.windows(8) {
+            let lanes = [w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7]];
+            let hashes = hash8(lanes).unwrap();
+            for (lane, kmer) in lanes.iter().enumerate() {
+                assert_eq!(hashes[lane], kmerhash(kmer).unwrap(), "lane {}, k = {}", lane, k);
+            }
+        }
+    }
+
+    let err = hash8([b"AC", b"AC", b"AC", b"AC", b"AC", b"AC", b"A", b"AC"]);
+    assert_eq!(err, Err(KmerError::LengthMismatch { expected: 2, found: 1 }));
+}
+
+#[test]
+fn simd_lookup_matches_scalar_for_every_byte() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    for k in [1, 7, 20, 21].iter().cloned() {
+        let kmers: Vec<&[u8]> = bytes.windows(k).collect();
+        for w in kmers.chunks_exact(4) {
+            let hashes = hash4((w[0], w[1], w[2], w[3])).unwrap();
+            assert_eq!(hashes, (kmerhash(w[0]).unwrap(), kmerhash(w[1]).unwrap(),
+                                kmerhash(w[2]).unwrap(), kmerhash(w[3]).unwrap()));
+        }
+        for w in kmers.chunks_exact(8) {
+            let hashes = hash8([w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7]]).unwrap();
+            for (hash, kmer) in hashes.iter().zip(w.iter()) {
+                assert_eq!(*hash, kmerhash(kmer).unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn hash_sequence_matches_rolling() {
+    let bytes: Vec<u8> = (0..=255).chain(SEQ.iter().cloned()).collect();
+    for len in (0..40).chain([100, 255, 256, 257, bytes.len()].iter().cloned()) {
+        let seq = &bytes[bytes.len() - len..];
+        for k in 1..=21 {
+            let expected: Vec<u64> = RollingHash::new(seq, k).unwrap().map(|(_, h)| h).collect();
+            let mut hashes = vec![1, 2, 3];
+            hash_sequence(seq, k, &mut hashes).unwrap();
+            assert_eq!(&hashes[..3], &[1, 2, 3]);
+            assert_eq!(&hashes[3..], &expected[..], "len = {}, k = {}", len, k);
+        }
+    }
+}
+
+#[test]
+fn hash_sequence_checks_lengths() {
+    assert_eq!(hash_sequence(SEQ, 0, &mut Vec::new()), Err(KmerError::Empty));
+    let mut hashes = [0; 3];
+    assert_eq!(
+        hash_sequence_into(b"ACGTA", 2, &mut hashes),
+        Err(KmerError::LengthMismatch { expected: 4, found: 3 })
+    );
+}
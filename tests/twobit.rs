@@ -0,0 +1,63 @@
+extern crate kmer_hasher;
+
+use kmer_hasher::*;
+
+const SEQ: &[u8] = b"ACGTNACGGTTCAGANNCTAGCTAGGGCATCGACTAGCATCGACTTTTACGACTAGCnacgtGATTACARYACCGGTTAAGCTTAGCATCG";
+
+#[test]
+fn packs_32_bases() {
+    let kmer = b"ACGTACGTACGTACGTACGTACGTACGTACGT";
+    assert_eq!(kmerhash_2bit(kmer).unwrap(), (0x1b1b_1b1b_1b1b_1b1b, 0));
+    assert_eq!(kmerhash_2bit(&[b'A'; 33]), Err(KmerError::TooLong { len: 33, max: 32 }));
+    assert_eq!(kmerhash_2bit(b""), Err(KmerError::Empty));
+}
+
+#[test]
+fn mask_marks_n() {
+    assert_eq!(kmerhash_2bit(b"ANA").unwrap(), (0, 0b010));
+    assert_eq!(kmerhash_2bit(b"NCGR").unwrap().1, 0b1001);
+    let mut kmer = [b'T'; 32];
+    kmer[0] = b'n';
+    assert_eq!(kmerhash_2bit(&kmer).unwrap().1, 1 << 31);
+}
+
+#[test]
+fn smallest_mask_follows_strand() {
+    let (_, mask, strand) = kmerhash_smallest_2bit(b"TTNG").unwrap();
+    assert_eq!(strand, Strand::Reverse);
+    assert_eq!(mask, 0b0100);
+    assert_eq!(kmerhash_smallest_2bit(b"ACGT").unwrap(), (0b00_01_10_11, 0, Strand::Forward));
+}
+
+#[test]
+fn rolling_matches_scalar() {
+    for k in 1..=32 {
+        let hashes: Vec<(usize, u64, u32)> = TwoBitRollingHash::new(SEQ, k).unwrap().collect();
+        let canonical: Vec<(usize, u64, u32, Strand)> = CanonicalTwoBitRollingHash::new(SEQ, k).unwrap().collect();
+        assert_eq!(hashes.len(), SEQ.len() - k + 1);
+        assert_eq!(canonical.len(), SEQ.len() - k + 1);
+        for (i, window) in SEQ.windows(k).enumerate() {
+            let (hash, mask) = kmerhash_2bit(window).unwrap();
+            let (smallest, smallest_mask, strand) = kmerhash_smallest_2bit(window).unwrap();
+            assert_eq!(hashes[i], (i, hash, mask), "k = {}", k);
+            assert_eq!(canonical[i], (i, smallest, smallest_mask, strand), "k = {}", k);
+        }
+    }
+}
+
+#[test]
+fn canonical_is_strand_independent_with_n() {
+    let rc_seq = reverse_complement(SEQ);
+    for k in 1..=32 {
+        let fwd: Vec<(usize, u64, u32, Strand)> = CanonicalTwoBitRollingHash::new(SEQ, k).unwrap().collect();
+        let rev: Vec<(usize, u64, u32, Strand)> = CanonicalTwoBitRollingHash::new(&rc_seq, k).unwrap().collect();
+        for (i, window) in SEQ.windows(k).enumerate() {
+            let (hash, mask, _) = kmerhash_smallest_2bit(window).unwrap();
+            let (rc_hash, rc_mask, _) = kmerhash_smallest_2bit(&reverse_complement(window)).unwrap();
+            assert_eq!((hash, mask), (rc_hash, rc_mask), "k = {}, window {}", k, i);
+
+            let j = SEQ.len() - k - i;
+            assert_eq!((fwd[i].1, fwd[i].2), (rev[j].1, rev[j].2), "k = {}, window {}", k, i);
+        }
+    }
+}